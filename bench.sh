@@ -0,0 +1,11 @@
+#!/usr/bin/env bash
+set -euo pipefail
+
+repo_root="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
+cd "${repo_root}"
+
+# Usage: ./bench.sh [from-to] [workers]. The pair must be installed.
+export QMAKE="${QMAKE:-/usr/bin/qmake}"
+
+BENCH_PAIR="${1:-en-nl}" BENCH_WORKERS="${2:-0}" \
+    cargo test --release batch_benchmark -- --ignored --nocapture
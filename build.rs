@@ -30,6 +30,8 @@ fn main() {
     }
 
     println!("cargo:rerun-if-changed=src/rendered_image_item.rs");
+    println!("cargo:rerun-if-changed=src/ui/clipboard.rs");
+    println!("cargo:rerun-if-changed=src/pdf_render.rs");
 
     let qt_include_path =
         std::env::var("DEP_QT_INCLUDE_PATH").expect("DEP_QT_INCLUDE_PATH missing");
@@ -39,7 +41,39 @@ fn main() {
             config.flag(flag);
         }
     }
+    if std::env::var_os("CARGO_FEATURE_PDF").is_some() {
+        link_poppler(&mut config);
+    }
     config
         .include(&qt_include_path)
-        .build("src/rendered_image_item.rs");
+        .build("src/main.rs");
+}
+
+/// PDF pages are rendered in-process with poppler's Qt bindings.
+fn link_poppler(config: &mut cpp_build::Config) {
+    let poppler = Command::new("pkg-config")
+        .args(["--cflags-only-I", "--libs", "poppler-qt5"])
+        .output();
+    let poppler = match poppler {
+        Ok(output) if output.status.success() => output,
+        Ok(output) => panic!(
+            "PDF support needs the poppler-qt5 development files (libpoppler-qt5-dev on \
+             Debian/Ubuntu, poppler-qt5-dev on Alpine); install them or build with \
+             --no-default-features to leave PDF support out. pkg-config: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(err) => panic!(
+            "PDF support needs pkg-config to find poppler-qt5; install it or build with \
+             --no-default-features to leave PDF support out: {err}"
+        ),
+    };
+    for flag in String::from_utf8_lossy(&poppler.stdout).split_whitespace() {
+        if let Some(path) = flag.strip_prefix("-I") {
+            config.include(path);
+        } else if let Some(path) = flag.strip_prefix("-L") {
+            println!("cargo:rustc-link-search=native={path}");
+        } else if let Some(lib) = flag.strip_prefix("-l") {
+            println!("cargo:rustc-link-lib={lib}");
+        }
+    }
 }
@@ -6,6 +6,7 @@ ComboBox {
     property var theme
     property string iconSource
     property bool desktopMode: false
+    property var translate: function(text) { return text }
     leftInset: 0
     rightInset: 0
     topInset: 0
@@ -19,7 +20,7 @@ ComboBox {
     contentItem: Label {
         leftPadding: ui.dp(6)
         rightPadding: control.indicator ? control.indicator.width + ui.dp(6) : ui.dp(6)
-        text: control.displayText
+        text: control.translate(control.displayText)
         color: theme.textPrimary
         verticalAlignment: Text.AlignVCenter
         elide: Text.ElideRight
@@ -59,7 +60,7 @@ ComboBox {
                 Label {
                     anchors.fill: parent
                     leftPadding: ui.dp(6)
-                    text: modelData
+                    text: control.translate(modelData)
                     color: theme.textPrimary
                     verticalAlignment: Text.AlignVCenter
                     font.pointSize: ui.pt(15)
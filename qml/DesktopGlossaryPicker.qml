@@ -0,0 +1,19 @@
+import QtQuick 2.15
+import QtQuick.Dialogs 1.3
+
+Item {
+    property var appBridge
+
+    function open() {
+        picker.open()
+    }
+
+    FileDialog {
+        id: picker
+        title: appBridge ? appBridge.tr("Choose a glossary") : "Choose a glossary"
+        nameFilters: ["Glossaries (*.csv *.tsv *.txt *.tbx *.xml)"]
+        selectExisting: true
+        selectMultiple: false
+        onAccepted: appBridge.import_glossary(fileUrl.toString())
+    }
+}
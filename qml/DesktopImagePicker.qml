@@ -10,10 +10,16 @@ Item {
 
     FileDialog {
         id: picker
-        title: "Choose an image"
-        nameFilters: ["Images (*.png *.jpg *.jpeg *.webp *.bmp *.gif *.tif *.tiff)"]
+        title: appBridge ? appBridge.tr("Choose images or a PDF") : "Choose images or a PDF"
+        nameFilters: ["Images and PDFs (*.png *.jpg *.jpeg *.webp *.bmp *.gif *.tif *.tiff *.pdf)"]
         selectExisting: true
-        selectMultiple: false
-        onAccepted: appBridge.process_image_selection(fileUrl.toString())
+        selectMultiple: true
+        onAccepted: {
+            var urls = []
+            for (var i = 0; i < fileUrls.length; i += 1) {
+                urls.push(fileUrls[i].toString())
+            }
+            appBridge.process_image_files(urls.join("\n"))
+        }
     }
 }
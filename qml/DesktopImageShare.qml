@@ -1,4 +1,5 @@
 import QtQuick 2.15
+import QtQuick.Dialogs 1.3
 
 Item {
     property var appBridge
@@ -9,4 +10,17 @@ Item {
         }
         Qt.openUrlExternally(url)
     }
+
+    function exportPageTexts() {
+        pageTextsDialog.open()
+    }
+
+    FileDialog {
+        id: pageTextsDialog
+        title: appBridge ? appBridge.tr("Export text") : "Export text"
+        nameFilters: ["Text (*.txt)"]
+        selectExisting: false
+        selectMultiple: false
+        onAccepted: appBridge.export_page_texts(fileUrl.toString())
+    }
 }
@@ -0,0 +1,34 @@
+import QtQuick 2.15
+import QtQuick.Dialogs 1.3
+
+Item {
+    property var appBridge
+    property bool includeGlossary: true
+
+    function exportSettings(withGlossary) {
+        includeGlossary = withGlossary
+        exportDialog.open()
+    }
+
+    function importSettings() {
+        importDialog.open()
+    }
+
+    FileDialog {
+        id: exportDialog
+        title: appBridge ? appBridge.tr("Export settings") : "Export settings"
+        nameFilters: ["Settings (*.json)"]
+        selectExisting: false
+        selectMultiple: false
+        onAccepted: appBridge.export_settings(fileUrl.toString(), includeGlossary)
+    }
+
+    FileDialog {
+        id: importDialog
+        title: appBridge ? appBridge.tr("Import settings") : "Import settings"
+        nameFilters: ["Settings (*.json)"]
+        selectExisting: true
+        selectMultiple: false
+        onAccepted: appBridge.import_settings(fileUrl.toString())
+    }
+}
@@ -80,7 +80,7 @@ Item {
                 Layout.topMargin: ui.dp(18)
                 Layout.leftMargin: ui.dp(20)
                 Layout.rightMargin: ui.dp(20)
-                text: "Pick a voice"
+                text: appBridge.tr("Pick a voice")
                 color: "white"
                 font.pointSize: ui.pt(22)
                 font.bold: true
@@ -156,7 +156,7 @@ Item {
                         Label {
                             anchors.centerIn: parent
                             visible: installed
-                            text: "Installed"
+                            text: appBridge.tr("Installed")
                             color: "#8A8E9F"
                             font.pointSize: ui.pt(12)
                         }
@@ -188,7 +188,7 @@ Item {
                     anchors.right: parent.right
                     anchors.rightMargin: ui.dp(18)
                     anchors.verticalCenter: parent.verticalCenter
-                    text: "Cancel"
+                    text: appBridge.tr("Cancel")
                     flat: true
                     onClicked: appBridge.close_tts_download_picker()
 
@@ -212,7 +212,7 @@ Item {
 
         TextField {
             Layout.fillWidth: true
-            placeholderText: "Filter languages"
+            placeholderText: appBridge.tr("Name, code or native name")
             text: appBridge.manage_filter_text
             color: theme.textPrimary
             placeholderTextColor: theme.textSecondary
@@ -422,7 +422,7 @@ Item {
                                 id: coreTitleLabel
                                 anchors.left: parent.left
                                 anchors.verticalCenter: parent.verticalCenter
-                                text: "Translation"
+                                text: appBridge.tr("Translation")
                                 color: theme.textPrimary
                                 font.pointSize: ui.sectionTitlePt
                             }
@@ -477,7 +477,7 @@ Item {
                                 id: dictionaryTitleLabel
                                 anchors.left: parent.left
                                 anchors.verticalCenter: parent.verticalCenter
-                                text: "Dictionary"
+                                text: appBridge.tr("Dictionary")
                                 color: theme.textPrimary
                                 font.pointSize: ui.sectionTitlePt
                             }
@@ -530,7 +530,7 @@ Item {
                                 id: ttsTitleLabel
                                 anchors.left: parent.left
                                 anchors.verticalCenter: parent.verticalCenter
-                                text: "Text-to-speech"
+                                text: appBridge.tr("Text-to-speech")
                                 color: theme.textPrimary
                                 font.pointSize: ui.sectionTitlePt
                             }
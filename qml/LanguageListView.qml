@@ -18,7 +18,7 @@ ScrollView {
 
         Label {
             visible: installedModel.rowCount() > 0
-            text: "Installed"
+            text: appBridge.tr("Installed")
             color: theme.textPrimary
             font.pointSize: ui.pt(18)
         }
@@ -42,7 +42,7 @@ ScrollView {
 
         Label {
             visible: availableModel.rowCount() > 0
-            text: "Available"
+            text: appBridge.tr("Available")
             color: theme.textPrimary
             font.pointSize: ui.pt(18)
         }
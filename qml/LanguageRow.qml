@@ -36,7 +36,7 @@ Rectangle {
             }
 
             Label {
-                text: parent.parent.parent.built_in ? "Built in" : parent.parent.parent.size
+                text: parent.parent.parent.built_in ? appBridge.tr("Built in") : parent.parent.parent.size
                 color: parent.parent.parent.theme.textSecondary
                 font.pointSize: ui.pt(13)
             }
@@ -53,7 +53,7 @@ Rectangle {
         Button {
             visible: parent.parent.download_progress <= 0
             enabled: !parent.parent.built_in || !parent.parent.installed
-            text: parent.parent.installed ? "Delete" : "Download"
+            text: appBridge.tr(parent.parent.installed ? "Delete" : "Download")
             onClicked: {
                 if (parent.parent.installed) {
                     parent.parent.appBridge.delete_language(parent.parent.code)
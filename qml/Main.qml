@@ -10,11 +10,22 @@ ApplicationWindow {
     height: app.desktop_mode ? 1024 : 1280
     minimumWidth: app.desktop_mode ? 600 : 360
     minimumHeight: app.desktop_mode ? 1024 : 640
-    title: "Offline Translator"
+    title: app.tr("Offline Translator")
+    onActiveChanged: if (active) app.window_activated()
 
     AppTheme { id: theme }
     color: theme.backgroundColor
 
+    Connections {
+        target: app
+
+        function onRaise_requested() {
+            root.show()
+            root.raise()
+            root.requestActivate()
+        }
+    }
+
     Item {
         id: appChrome
         anchors.fill: parent
@@ -46,7 +57,7 @@ ApplicationWindow {
                 return
             }
             app.automation_log("watch output='" + app.output_text + "'")
-            if (app.output_text.length === 0 || app.output_text === "Running OCR...") {
+            if (app.output_text.length === 0 || app.output_text === app.tr("Running OCR...")) {
                 return
             }
             automationPending = false
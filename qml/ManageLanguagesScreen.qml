@@ -17,7 +17,7 @@ Item {
             Layout.fillWidth: true
             appBridge: root.appBridge
             theme: root.theme
-            title: "Manage languages"
+            title: appBridge.tr("Manage languages")
             onBackRequested: appBridge.back_from_manage_languages()
         }
 
@@ -0,0 +1,30 @@
+import QtQuick 2.15
+import QtQuick.Controls 2.15
+
+Rectangle {
+    id: root
+    property alias text: label.text
+    property bool active: false
+    property color activeColor: "#A8BCFF"
+    signal clicked()
+    UiScale { id: ui }
+
+    width: label.implicitWidth + ui.dp(24)
+    radius: height / 2
+    color: mouseArea.pressed ? "#99000000" : "#80000000"
+    border.color: root.active ? root.activeColor : "transparent"
+    border.width: 1
+
+    Label {
+        id: label
+        anchors.centerIn: parent
+        color: root.active ? root.activeColor : "white"
+        font.pointSize: ui.pt(11)
+    }
+
+    MouseArea {
+        id: mouseArea
+        anchors.fill: parent
+        onClicked: root.clicked()
+    }
+}
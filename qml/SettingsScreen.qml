@@ -9,8 +9,46 @@ Item {
     UiScale { id: ui; desktopMode: root.appBridge && root.appBridge.desktop_mode }
 
     property bool advancedExpanded: false
+    property bool includeGlossaryInExport: true
+    property bool resetArmed: false
     property string expandMoreIcon: appBridge.asset_url("expand_more.svg")
 
+    Loader {
+        id: glossaryPickerLoader
+        active: true
+        parent: appBridge.desktop_mode ? root : Overlay.overlay
+        anchors.fill: parent
+        z: 30
+        source: appBridge.desktop_mode ? "DesktopGlossaryPicker.qml" : "UbportsGlossaryPicker.qml"
+
+        onLoaded: {
+            if (item) {
+                item.appBridge = appBridge
+            }
+        }
+    }
+
+    Loader {
+        id: settingsTransferLoader
+        active: true
+        parent: appBridge.desktop_mode ? root : Overlay.overlay
+        anchors.fill: parent
+        z: 30
+        source: appBridge.desktop_mode ? "DesktopSettingsTransfer.qml" : "UbportsSettingsTransfer.qml"
+
+        onLoaded: {
+            if (item) {
+                item.appBridge = appBridge
+            }
+        }
+    }
+
+    Timer {
+        id: resetDisarmTimer
+        interval: 4000
+        onTriggered: root.resetArmed = false
+    }
+
     Flickable {
         anchors.fill: parent
         contentWidth: width
@@ -26,7 +64,7 @@ Item {
                 Layout.fillWidth: true
                 appBridge: root.appBridge
                 theme: root.theme
-                title: "Settings"
+                title: appBridge.tr("Settings")
                 onBackRequested: appBridge.back_from_settings()
             }
 
@@ -44,18 +82,18 @@ Item {
                     anchors { left: parent.left; right: parent.right; top: parent.top; margins: ui.dp(16) }
                     spacing: ui.dp(12)
 
-                    Label { text: "Languages"; color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
+                    Label { text: appBridge.tr("Languages"); color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
 
                     Item {
                         Layout.fillWidth: true; implicitHeight: ui.dp(28)
 
                         Label {
                             anchors.left: parent.left; anchors.verticalCenter: parent.verticalCenter
-                            text: "Language Packs"; color: theme.textPrimary; font.pointSize: ui.pt(15)
+                            text: appBridge.tr("Language Packs"); color: theme.textPrimary; font.pointSize: ui.pt(15)
                         }
                         Label {
                             anchors.right: parent.right; anchors.verticalCenter: parent.verticalCenter
-                            text: "Manage"; color: theme.accentColor; font.pointSize: ui.pt(15)
+                            text: appBridge.tr("Manage"); color: theme.accentColor; font.pointSize: ui.pt(15)
                             MouseArea { anchors.fill: parent; cursorShape: Qt.PointingHandCursor; onClicked: appBridge.show_manage_languages() }
                         }
                     }
@@ -76,11 +114,11 @@ Item {
                     anchors { left: parent.left; right: parent.right; top: parent.top; margins: ui.dp(16) }
                     spacing: ui.dp(16)
 
-                    Label { text: "General"; color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
+                    Label { text: appBridge.tr("General"); color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
 
                     ColumnLayout {
                         Layout.fillWidth: true; spacing: ui.dp(6)
-                        Label { text: "Default 'from' language"; color: theme.textSecondary; font.pointSize: ui.pt(13) }
+                        Label { text: appBridge.tr("Default 'from' language"); color: theme.textSecondary; font.pointSize: ui.pt(13) }
                         DarkComboBox {
                             Layout.fillWidth: true; Layout.preferredHeight: ui.dp(40)
                             desktopMode: root.appBridge.desktop_mode
@@ -93,7 +131,7 @@ Item {
 
                     ColumnLayout {
                         Layout.fillWidth: true; spacing: ui.dp(6)
-                        Label { text: "Default 'to' language"; color: theme.textSecondary; font.pointSize: ui.pt(13) }
+                        Label { text: appBridge.tr("Default 'to' language"); color: theme.textSecondary; font.pointSize: ui.pt(13) }
                         DarkComboBox {
                             Layout.fillWidth: true; Layout.preferredHeight: ui.dp(40)
                             desktopMode: root.appBridge.desktop_mode
@@ -121,16 +159,17 @@ Item {
                     anchors { left: parent.left; right: parent.right; top: parent.top; margins: ui.dp(16) }
                     spacing: ui.dp(16)
 
-                    Label { text: "OCR"; color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
+                    Label { text: appBridge.tr("OCR"); color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
 
                     ColumnLayout {
                         Layout.fillWidth: true; spacing: ui.dp(6)
-                        Label { text: "Background Mode"; color: theme.textSecondary; font.pointSize: ui.pt(13) }
+                        Label { text: appBridge.tr("Background Mode"); color: theme.textSecondary; font.pointSize: ui.pt(13) }
                         DarkComboBox {
                             Layout.fillWidth: true; Layout.preferredHeight: ui.dp(40)
                             desktopMode: root.appBridge.desktop_mode
                             theme: root.theme; iconSource: expandMoreIcon
                             model: ["Auto-detect Colors", "Light Background", "Dark Background"]
+                            translate: function(text) { return appBridge.tr(text) }
                             Component.onCompleted: { var idx = find(appBridge.ocr_background_mode); if (idx >= 0) currentIndex = idx }
                             onActivated: appBridge.set_ocr_background_mode_value(currentText)
                         }
@@ -138,7 +177,29 @@ Item {
 
                     ColumnLayout {
                         Layout.fillWidth: true; spacing: ui.dp(6)
-                        Label { text: "Min Confidence: " + appBridge.ocr_min_confidence + "%"; color: theme.textSecondary; font.pointSize: ui.pt(13) }
+                        Label { text: appBridge.tr("Reading Order"); color: theme.textSecondary; font.pointSize: ui.pt(13) }
+                        DarkComboBox {
+                            Layout.fillWidth: true; Layout.preferredHeight: ui.dp(40)
+                            desktopMode: root.appBridge.desktop_mode
+                            theme: root.theme; iconSource: expandMoreIcon
+                            model: ["Automatic", "Left to right", "Right to left", "Vertical"]
+                            translate: function(text) { return appBridge.tr(text) }
+                            Component.onCompleted: { var idx = find(appBridge.ocr_reading_order); if (idx >= 0) currentIndex = idx }
+                            onActivated: appBridge.set_ocr_reading_order_value(currentText)
+                        }
+                    }
+
+                    DarkSwitch {
+                        Layout.fillWidth: true; theme: root.theme
+                        desktopMode: root.appBridge.desktop_mode
+                        label: appBridge.tr("Straighten tilted and sideways photos")
+                        checked: appBridge.ocr_straighten
+                        onToggled: appBridge.set_ocr_straighten_value(checked)
+                    }
+
+                    ColumnLayout {
+                        Layout.fillWidth: true; spacing: ui.dp(6)
+                        Label { text: appBridge.tr("Min Confidence: {value}%").replace("{value}", appBridge.ocr_min_confidence); color: theme.textSecondary; font.pointSize: ui.pt(13) }
                         DarkSlider {
                             Layout.fillWidth: true; Layout.preferredHeight: ui.dp(28)
                             theme: root.theme
@@ -150,7 +211,7 @@ Item {
 
                     ColumnLayout {
                         Layout.fillWidth: true; spacing: ui.dp(6)
-                        Label { text: "Max Image Size: " + appBridge.ocr_max_image_size + "px"; color: theme.textSecondary; font.pointSize: ui.pt(13) }
+                        Label { text: appBridge.tr("Max Image Size: {value}px").replace("{value}", appBridge.ocr_max_image_size); color: theme.textSecondary; font.pointSize: ui.pt(13) }
                         DarkSlider {
                             Layout.fillWidth: true; Layout.preferredHeight: ui.dp(28)
                             theme: root.theme
@@ -164,6 +225,305 @@ Item {
 
             Item { Layout.preferredHeight: ui.dp(16) }
 
+            // ── Protected Text ──
+            Rectangle {
+                Layout.fillWidth: true
+                Layout.leftMargin: ui.dp(16); Layout.rightMargin: ui.dp(16)
+                implicitHeight: protectCol.implicitHeight + ui.dp(32)
+                radius: ui.dp(12); color: theme.surfaceColor
+
+                ColumnLayout {
+                    id: protectCol
+                    anchors { left: parent.left; right: parent.right; top: parent.top; margins: ui.dp(16) }
+                    spacing: ui.dp(16)
+
+                    Label { text: appBridge.tr("Protected Text"); color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
+
+                    Label {
+                        Layout.fillWidth: true
+                        text: appBridge.tr("Matching text is kept exactly as written instead of being translated")
+                        color: theme.textSecondary; font.pointSize: ui.pt(13); wrapMode: Text.Wrap
+                    }
+
+                    DarkSwitch {
+                        Layout.fillWidth: true; theme: root.theme
+                        desktopMode: root.appBridge.desktop_mode
+                        label: appBridge.tr("Placeholders like {user}")
+                        checked: appBridge.protect_braces_placeholders
+                        onToggled: appBridge.set_placeholder_protection_value("braces", checked)
+                    }
+
+                    DarkSwitch {
+                        Layout.fillWidth: true; theme: root.theme
+                        desktopMode: root.appBridge.desktop_mode
+                        label: appBridge.tr("Format specifiers like %d")
+                        checked: appBridge.protect_printf_placeholders
+                        onToggled: appBridge.set_placeholder_protection_value("printf", checked)
+                    }
+
+                    DarkSwitch {
+                        Layout.fillWidth: true; theme: root.theme
+                        desktopMode: root.appBridge.desktop_mode
+                        label: appBridge.tr("URLs")
+                        checked: appBridge.protect_urls
+                        onToggled: appBridge.set_placeholder_protection_value("urls", checked)
+                    }
+
+                    DarkSwitch {
+                        Layout.fillWidth: true; theme: root.theme
+                        desktopMode: root.appBridge.desktop_mode
+                        label: appBridge.tr("Email addresses")
+                        checked: appBridge.protect_emails
+                        onToggled: appBridge.set_placeholder_protection_value("emails", checked)
+                    }
+
+                    DarkSwitch {
+                        Layout.fillWidth: true; theme: root.theme
+                        desktopMode: root.appBridge.desktop_mode
+                        label: appBridge.tr("Numbers with units")
+                        checked: appBridge.protect_numbers_with_units
+                        onToggled: appBridge.set_placeholder_protection_value("units", checked)
+                    }
+                }
+            }
+
+            Item { Layout.preferredHeight: ui.dp(16) }
+
+            // ── Glossary ──
+            Rectangle {
+                Layout.fillWidth: true
+                Layout.leftMargin: ui.dp(16); Layout.rightMargin: ui.dp(16)
+                implicitHeight: glossaryCol.implicitHeight + ui.dp(32)
+                radius: ui.dp(12); color: theme.surfaceColor
+
+                ColumnLayout {
+                    id: glossaryCol
+                    anchors { left: parent.left; right: parent.right; top: parent.top; margins: ui.dp(16) }
+                    spacing: ui.dp(12)
+
+                    Item {
+                        Layout.fillWidth: true; implicitHeight: ui.dp(28)
+
+                        Label {
+                            anchors.left: parent.left; anchors.verticalCenter: parent.verticalCenter
+                            text: appBridge.tr("Glossary"); color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true
+                        }
+                        Label {
+                            anchors.right: parent.right; anchors.verticalCenter: parent.verticalCenter
+                            text: appBridge.tr("Import"); color: theme.accentColor; font.pointSize: ui.pt(15)
+                            MouseArea {
+                                anchors.fill: parent; cursorShape: Qt.PointingHandCursor
+                                onClicked: if (glossaryPickerLoader.item) glossaryPickerLoader.item.open()
+                            }
+                        }
+                    }
+
+                    Label {
+                        Layout.fillWidth: true
+                        text: appBridge.glossary_pair_label + " · leave the translation empty to keep a term as written"
+                        color: theme.textSecondary; font.pointSize: ui.pt(13); wrapMode: Text.Wrap
+                    }
+
+                    Label {
+                        Layout.fillWidth: true
+                        visible: appBridge.glossary_status.length > 0
+                        text: appBridge.glossary_status
+                        color: theme.textSecondary; font.pointSize: ui.pt(13); wrapMode: Text.Wrap
+                    }
+
+                    Repeater {
+                        model: appBridge.glossary_model
+
+                        Item {
+                            Layout.fillWidth: true; implicitHeight: ui.dp(32)
+
+                            Label {
+                                anchors.left: parent.left; anchors.right: removeTerm.left; anchors.rightMargin: ui.dp(8)
+                                anchors.verticalCenter: parent.verticalCenter
+                                text: source + " → " + (keep_original ? "(do not translate)" : target)
+                                color: theme.textPrimary; font.pointSize: ui.pt(14); elide: Text.ElideRight
+                            }
+                            FeedbackIconButton {
+                                id: removeTerm
+                                anchors.right: parent.right; anchors.verticalCenter: parent.verticalCenter
+                                width: ui.dp(28); height: ui.dp(28); iconSize: ui.dp(20)
+                                iconSource: appBridge.asset_url("delete.svg")
+                                onClicked: appBridge.remove_glossary_entry(index)
+                            }
+                        }
+                    }
+
+                    RowLayout {
+                        Layout.fillWidth: true; spacing: ui.dp(8)
+
+                        TextField {
+                            id: glossarySource
+                            Layout.fillWidth: true
+                            placeholderText: appBridge.tr("Term")
+                            color: theme.textPrimary
+                            placeholderTextColor: theme.textSecondary
+                            font.pointSize: ui.pt(14)
+                            background: Rectangle { radius: ui.dp(8); color: theme.backgroundElevated; border.width: 1; border.color: theme.borderColor }
+                        }
+                        TextField {
+                            id: glossaryTarget
+                            Layout.fillWidth: true
+                            placeholderText: appBridge.tr("Translation")
+                            color: theme.textPrimary
+                            placeholderTextColor: theme.textSecondary
+                            font.pointSize: ui.pt(14)
+                            background: Rectangle { radius: ui.dp(8); color: theme.backgroundElevated; border.width: 1; border.color: theme.borderColor }
+                        }
+                        Label {
+                            text: appBridge.tr("Add"); color: glossarySource.text.trim().length > 0 ? theme.accentColor : theme.textSecondary
+                            font.pointSize: ui.pt(15)
+                            MouseArea {
+                                anchors.fill: parent; cursorShape: Qt.PointingHandCursor
+                                enabled: glossarySource.text.trim().length > 0
+                                onClicked: {
+                                    appBridge.add_glossary_entry(glossarySource.text, glossaryTarget.text)
+                                    glossarySource.text = ""
+                                    glossaryTarget.text = ""
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            Item { Layout.preferredHeight: ui.dp(16) }
+
+            // ── Local API ──
+            Rectangle {
+                Layout.fillWidth: true
+                Layout.leftMargin: ui.dp(16); Layout.rightMargin: ui.dp(16)
+                implicitHeight: apiCol.implicitHeight + ui.dp(32)
+                radius: ui.dp(12); color: theme.surfaceColor
+
+                ColumnLayout {
+                    id: apiCol
+                    anchors { left: parent.left; right: parent.right; top: parent.top; margins: ui.dp(16) }
+                    spacing: ui.dp(16)
+
+                    Label { text: appBridge.tr("Local API"); color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
+
+                    Label {
+                        Layout.fillWidth: true
+                        text: appBridge.tr("LibreTranslate-compatible server for other apps on this device. Only reachable from localhost.")
+                        color: theme.textSecondary; font.pointSize: ui.pt(13); wrapMode: Text.Wrap
+                    }
+
+                    DarkSwitch {
+                        Layout.fillWidth: true; theme: root.theme
+                        desktopMode: root.appBridge.desktop_mode
+                        label: appBridge.tr("Enable local API")
+                        checked: appBridge.api_server_enabled
+                        onToggled: appBridge.set_api_server_enabled_value(checked)
+                    }
+
+                    ColumnLayout {
+                        Layout.fillWidth: true; spacing: ui.dp(6)
+                        Label { text: appBridge.tr("Port"); color: theme.textSecondary; font.pointSize: ui.pt(13) }
+                        TextField {
+                            Layout.fillWidth: true
+                            text: appBridge.api_server_port
+                            inputMethodHints: Qt.ImhDigitsOnly
+                            validator: IntValidator { bottom: 1024; top: 65535 }
+                            color: theme.textPrimary
+                            placeholderTextColor: theme.textSecondary
+                            font.pointSize: ui.pt(14)
+                            onEditingFinished: appBridge.set_api_server_port_value(parseInt(text))
+                            background: Rectangle { radius: ui.dp(8); color: theme.backgroundElevated; border.width: 1; border.color: theme.borderColor }
+                        }
+                    }
+
+                    Label {
+                        visible: appBridge.api_server_status.length > 0
+                        Layout.fillWidth: true
+                        text: appBridge.api_server_status
+                        color: theme.textSecondary; font.pointSize: ui.pt(13); wrapMode: Text.Wrap
+                    }
+                }
+            }
+
+            Item { Layout.preferredHeight: ui.dp(16) }
+
+            // ── Backup ──
+            Rectangle {
+                Layout.fillWidth: true
+                Layout.leftMargin: ui.dp(16); Layout.rightMargin: ui.dp(16)
+                implicitHeight: backupCol.implicitHeight + ui.dp(32)
+                radius: ui.dp(12); color: theme.surfaceColor
+
+                ColumnLayout {
+                    id: backupCol
+                    anchors { left: parent.left; right: parent.right; top: parent.top; margins: ui.dp(16) }
+                    spacing: ui.dp(16)
+
+                    Label { text: appBridge.tr("Backup"); color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true }
+
+                    Label {
+                        Layout.fillWidth: true
+                        text: appBridge.tr("Move your preferences to another device as a single file.")
+                        color: theme.textSecondary; font.pointSize: ui.pt(13); wrapMode: Text.Wrap
+                    }
+
+                    DarkSwitch {
+                        Layout.fillWidth: true; theme: root.theme
+                        desktopMode: root.appBridge.desktop_mode
+                        label: appBridge.tr("Include glossary")
+                        checked: root.includeGlossaryInExport
+                        onToggled: root.includeGlossaryInExport = checked
+                    }
+
+                    RowLayout {
+                        Layout.fillWidth: true; spacing: ui.dp(24)
+
+                        Label {
+                            text: appBridge.tr("Export"); color: theme.accentColor; font.pointSize: ui.pt(15)
+                            MouseArea {
+                                anchors.fill: parent; cursorShape: Qt.PointingHandCursor
+                                onClicked: if (settingsTransferLoader.item) settingsTransferLoader.item.exportSettings(root.includeGlossaryInExport)
+                            }
+                        }
+                        Label {
+                            text: appBridge.tr("Import"); color: theme.accentColor; font.pointSize: ui.pt(15)
+                            MouseArea {
+                                anchors.fill: parent; cursorShape: Qt.PointingHandCursor
+                                onClicked: if (settingsTransferLoader.item) settingsTransferLoader.item.importSettings()
+                            }
+                        }
+                        Item { Layout.fillWidth: true }
+                        Label {
+                            text: root.resetArmed ? appBridge.tr("Tap again to reset") : appBridge.tr("Reset to defaults")
+                            color: root.resetArmed ? theme.textPrimary : theme.textSecondary; font.pointSize: ui.pt(15)
+                            MouseArea {
+                                anchors.fill: parent; cursorShape: Qt.PointingHandCursor
+                                onClicked: {
+                                    if (root.resetArmed) {
+                                        root.resetArmed = false
+                                        resetDisarmTimer.stop()
+                                        appBridge.reset_settings()
+                                    } else {
+                                        root.resetArmed = true
+                                        resetDisarmTimer.restart()
+                                    }
+                                }
+                            }
+                        }
+                    }
+
+                    Label {
+                        visible: appBridge.settings_transfer_status.length > 0
+                        Layout.fillWidth: true
+                        text: appBridge.settings_transfer_status
+                        color: theme.textSecondary; font.pointSize: ui.pt(13); wrapMode: Text.Wrap
+                    }
+                }
+            }
+
+            Item { Layout.preferredHeight: ui.dp(16) }
+
             // ── Advanced Settings ──
             Rectangle {
                 Layout.fillWidth: true
@@ -181,7 +541,7 @@ Item {
 
                         Label {
                             anchors.left: parent.left; anchors.verticalCenter: parent.verticalCenter
-                            text: "Advanced Settings"; color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true
+                            text: appBridge.tr("Advanced Settings"); color: theme.accentColor; font.pointSize: ui.pt(18); font.bold: true
                         }
                         Image {
                             anchors.right: parent.right; anchors.verticalCenter: parent.verticalCenter
@@ -199,7 +559,7 @@ Item {
 
                         ColumnLayout {
                             Layout.fillWidth: true; spacing: ui.dp(6)
-                            Label { text: "Catalog Index URL"; color: theme.textSecondary; font.pointSize: ui.pt(13) }
+                            Label { text: appBridge.tr("Catalog Index URL"); color: theme.textSecondary; font.pointSize: ui.pt(13) }
                             TextField {
                                 Layout.fillWidth: true
                                 text: appBridge.catalog_index_url
@@ -214,7 +574,7 @@ Item {
                         DarkSwitch {
                             Layout.fillWidth: true; theme: root.theme
                             desktopMode: root.appBridge.desktop_mode
-                            label: "Disable OCR"
+                            label: appBridge.tr("Disable OCR")
                             checked: appBridge.disable_ocr
                             onToggled: appBridge.set_disable_ocr_value(checked)
                         }
@@ -222,7 +582,7 @@ Item {
                         DarkSwitch {
                             Layout.fillWidth: true; theme: root.theme
                             desktopMode: root.appBridge.desktop_mode
-                            label: "Disable automatic language detection"
+                            label: appBridge.tr("Disable automatic language detection")
                             checked: appBridge.disable_auto_detect
                             onToggled: appBridge.set_disable_auto_detect_value(checked)
                         }
@@ -230,7 +590,63 @@ Item {
                         DarkSwitch {
                             Layout.fillWidth: true; theme: root.theme
                             desktopMode: root.appBridge.desktop_mode
-                            label: "Show transliteration for output"
+                            label: appBridge.tr("Show language names in their own language")
+                            checked: appBridge.native_language_names
+                            onToggled: appBridge.set_native_language_names_value(checked)
+                        }
+
+                        DarkSwitch {
+                            Layout.fillWidth: true; theme: root.theme
+                            desktopMode: root.appBridge.desktop_mode
+                            label: appBridge.tr("Switch source language automatically")
+                            visible: !appBridge.disable_auto_detect
+                            checked: appBridge.auto_switch_source
+                            onToggled: appBridge.set_auto_switch_source_value(checked)
+                        }
+
+                        DarkSwitch {
+                            Layout.fillWidth: true; theme: root.theme
+                            desktopMode: root.appBridge.desktop_mode
+                            label: appBridge.tr("Translate clipboard when the app is focused")
+                            checked: appBridge.translate_clipboard_on_focus
+                            onToggled: appBridge.set_translate_clipboard_on_focus_value(checked)
+                        }
+
+                        DarkSwitch {
+                            Layout.fillWidth: true; theme: root.theme
+                            desktopMode: root.appBridge.desktop_mode
+                            label: appBridge.tr("Use primary selection instead of clipboard")
+                            checked: appBridge.clipboard_use_selection
+                            onToggled: appBridge.set_clipboard_use_selection_value(checked)
+                        }
+
+                        DarkSwitch {
+                            Layout.fillWidth: true; theme: root.theme
+                            desktopMode: root.appBridge.desktop_mode
+                            label: appBridge.tr("Pivot non-English pairs inside the engine")
+                            checked: appBridge.direct_translation
+                            onToggled: appBridge.set_direct_translation_value(checked)
+                        }
+
+                        ColumnLayout {
+                            Layout.fillWidth: true; spacing: ui.dp(6)
+                            Label {
+                                text: appBridge.tr("Translation workers: {value}").replace("{value}", appBridge.batch_workers === 0 ? appBridge.tr("Auto") : appBridge.batch_workers)
+                                color: theme.textSecondary; font.pointSize: ui.pt(13)
+                            }
+                            DarkSlider {
+                                Layout.fillWidth: true; Layout.preferredHeight: ui.dp(28)
+                                theme: root.theme
+                                from: 0; to: appBridge.max_batch_workers; stepSize: 1
+                                value: appBridge.batch_workers
+                                onMoved: appBridge.set_batch_workers_value(value)
+                            }
+                        }
+
+                        DarkSwitch {
+                            Layout.fillWidth: true; theme: root.theme
+                            desktopMode: root.appBridge.desktop_mode
+                            label: appBridge.tr("Show transliteration for output")
                             checked: appBridge.show_transliteration_output
                             onToggled: appBridge.set_show_transliteration_output_value(checked)
                         }
@@ -238,7 +654,7 @@ Item {
                         DarkSwitch {
                             Layout.fillWidth: true; theme: root.theme
                             desktopMode: root.appBridge.desktop_mode
-                            label: "Show transliteration for input"
+                            label: appBridge.tr("Show transliteration for input")
                             checked: appBridge.show_transliteration_input
                             onToggled: appBridge.set_show_transliteration_input_value(checked)
                         }
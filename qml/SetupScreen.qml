@@ -17,7 +17,7 @@ Item {
             Layout.fillWidth: true
 
             Label {
-                text: "Language Setup"
+                text: appBridge.tr("Language Setup")
                 color: theme.textPrimary
                 font.pointSize: ui.pt(22)
                 Layout.fillWidth: true
@@ -34,7 +34,7 @@ Item {
 
         Label {
             Layout.fillWidth: true
-            text: "Download language packs to start translating"
+            text: appBridge.tr("Download language packs to start translating")
             color: theme.textSecondary
             wrapMode: Text.WordWrap
         }
@@ -50,7 +50,7 @@ Item {
         Button {
             Layout.fillWidth: true
             enabled: appBridge.has_languages
-            text: "Done"
+            text: appBridge.tr("Done")
             onClicked: appBridge.finish_language_setup()
         }
     }
@@ -7,6 +7,7 @@ Item {
     property int imageMargin: 0
     property bool interactive: false
     signal imageClicked()
+    signal blockPressAndHold(int index, string sourceText)
     UiScale { id: ui }
 
     Item {
@@ -65,12 +66,16 @@ Item {
                  && root.appBridge.processed_image_height > 0
 
             Repeater {
+                id: overlayRepeater
                 model: root.appBridge ? root.appBridge.image_overlay_model : null
 
                 Item {
                     id: blockItem
                     width: overlayLayer.width
                     height: overlayLayer.height
+                    readonly property string sourceText: source_text
+                    readonly property rect blockRect: Qt.rect(block_x, block_y, block_width, block_height)
+                    readonly property string blockTranslation: translated_text
                     property var lineRects: []
                     property var fittedLines: []
                     property real fittedPixelSize: Math.max(8, Math.floor(suggested_font_size_px))
@@ -225,6 +230,18 @@ Item {
                     }
 
                     Component.onCompleted: rebuildLayout()
+                    onBlockTranslationChanged: rebuildLayout()
+
+                    Rectangle {
+                        visible: !translated_text && root.appBridge.ocr_only
+                        x: block_x * overlayLayer.width / root.appBridge.processed_image_width
+                        y: block_y * overlayLayer.height / root.appBridge.processed_image_height
+                        width: block_width * overlayLayer.width / root.appBridge.processed_image_width
+                        height: block_height * overlayLayer.height / root.appBridge.processed_image_height
+                        color: "transparent"
+                        border.color: "#A8BCFF"
+                        border.width: 2
+                    }
 
                     TextMetrics {
                         id: metrics
@@ -242,6 +259,7 @@ Item {
                             text: blockItem.fittedLines[index]
                             color: lineRect && lineRect.foreground_color ? lineRect.foreground_color : foreground_color
                             wrapMode: Text.NoWrap
+                            horizontalAlignment: right_to_left ? Text.AlignRight : Text.AlignLeft
                             clip: true
                             font.pixelSize: Math.max(
                                 8,
@@ -255,12 +273,145 @@ Item {
             }
         }
 
+        Item {
+            id: regionLayer
+            x: paintedBounds.x
+            y: paintedBounds.y
+            width: paintedBounds.width
+            height: paintedBounds.height
+            visible: root.appBridge && root.appBridge.select_regions
+            property real imageScale: root.appBridge && root.appBridge.processed_image_width > 0
+                                      ? width / root.appBridge.processed_image_width
+                                      : 0
+            property var regions: {
+                try {
+                    var parsed = JSON.parse(root.appBridge.image_regions)
+                    return Array.isArray(parsed) ? parsed : []
+                } catch (error) {
+                    return []
+                }
+            }
+
+            Repeater {
+                model: regionLayer.regions.length
+
+                Rectangle {
+                    property var region: regionLayer.regions[index]
+                    x: region.x * regionLayer.imageScale
+                    y: region.y * regionLayer.imageScale
+                    width: region.width * regionLayer.imageScale
+                    height: region.height * regionLayer.imageScale
+                    color: "transparent"
+                    border.color: "#A8BCFF"
+                    border.width: 2
+                }
+            }
+
+            Rectangle {
+                id: draftRegion
+                visible: regionDrawer.pressed
+                color: "#33A8BCFF"
+                border.color: "#A8BCFF"
+                border.width: 1
+            }
+
+            MouseArea {
+                id: regionDrawer
+                property point origin
+                anchors.fill: parent
+                enabled: root.interactive && regionLayer.imageScale > 0
+                preventStealing: true
+
+                onPressed: {
+                    origin = Qt.point(mouse.x, mouse.y)
+                    draftRegion.x = mouse.x
+                    draftRegion.y = mouse.y
+                    draftRegion.width = 0
+                    draftRegion.height = 0
+                }
+                onPositionChanged: {
+                    var x = Math.max(0, Math.min(mouse.x, width))
+                    var y = Math.max(0, Math.min(mouse.y, height))
+                    draftRegion.x = Math.min(origin.x, x)
+                    draftRegion.y = Math.min(origin.y, y)
+                    draftRegion.width = Math.abs(x - origin.x)
+                    draftRegion.height = Math.abs(y - origin.y)
+                }
+                onReleased: {
+                    if (draftRegion.width < ui.dp(12) || draftRegion.height < ui.dp(12)) {
+                        return
+                    }
+                    root.appBridge.add_image_region(
+                                draftRegion.x / regionLayer.imageScale,
+                                draftRegion.y / regionLayer.imageScale,
+                                draftRegion.width / regionLayer.imageScale,
+                                draftRegion.height / regionLayer.imageScale)
+                }
+            }
+        }
+
+        Item {
+            id: cornerLayer
+            x: paintedBounds.x
+            y: paintedBounds.y
+            width: paintedBounds.width
+            height: paintedBounds.height
+            visible: root.appBridge && root.appBridge.picking_corners
+            property var points: {
+                try {
+                    var parsed = JSON.parse(root.appBridge.corner_points)
+                    return Array.isArray(parsed) ? parsed : []
+                } catch (error) {
+                    return []
+                }
+            }
+
+            Repeater {
+                model: cornerLayer.points.length
+
+                Rectangle {
+                    property var point: cornerLayer.points[index]
+                    x: point.x * paintedBounds.scaleFactor - width / 2
+                    y: point.y * paintedBounds.scaleFactor - height / 2
+                    width: ui.dp(14)
+                    height: ui.dp(14)
+                    radius: width / 2
+                    color: "#A8BCFF"
+                    border.color: "white"
+                    border.width: 2
+                }
+            }
+
+            MouseArea {
+                anchors.fill: parent
+                enabled: root.interactive && paintedBounds.scaleFactor > 0
+                onClicked: root.appBridge.add_perspective_corner(
+                               mouse.x / paintedBounds.scaleFactor,
+                               mouse.y / paintedBounds.scaleFactor)
+            }
+        }
+
         MouseArea {
-            visible: root.interactive && paintedBounds.width > 0 && paintedBounds.height > 0
+            visible: root.interactive
+                     && !(root.appBridge && (root.appBridge.select_regions || root.appBridge.picking_corners))
+                     && paintedBounds.width > 0 && paintedBounds.height > 0
             x: paintedBounds.x
             y: paintedBounds.y
             width: paintedBounds.width
             height: paintedBounds.height
             onClicked: root.imageClicked()
+            onPressAndHold: {
+                var imageX = mouse.x * root.appBridge.processed_image_width / width
+                var imageY = mouse.y * root.appBridge.processed_image_height / height
+                for (var index = 0; index < overlayRepeater.count; index += 1) {
+                    var block = overlayRepeater.itemAt(index)
+                    if (block
+                            && imageX >= block.blockRect.x && imageX <= block.blockRect.x + block.blockRect.width
+                            && imageY >= block.blockRect.y && imageY <= block.blockRect.y + block.blockRect.height) {
+                        root.blockPressAndHold(index, block.sourceText)
+                        return
+                    }
+                }
+            }
         }
   }
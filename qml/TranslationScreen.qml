@@ -14,10 +14,15 @@ Item {
     readonly property real imageOverlayIconSize: appBridge.desktop_mode ? ui.dp(18) : ui.dp(20)
     readonly property real fullscreenOverlayButtonSize: ui.dp(40)
     readonly property real fullscreenOverlayIconSize: ui.dp(20)
+    readonly property bool editingExtractedText: appBridge.image_mode
+                                                 && appBridge.ocr_only
+                                                 && appBridge.output_text.length === 0
 
     function shareCurrentImage() {
         if (imageShareLoader.item) {
-            imageShareLoader.item.share(appBridge.share_image_url || appBridge.selected_image_url)
+            imageShareLoader.item.share(appBridge.export_translated_image()
+                                        || appBridge.share_image_url
+                                        || appBridge.selected_image_url)
         }
     }
 
@@ -76,6 +81,58 @@ Item {
         anchors.bottomMargin: ui.dp(16)
         spacing: ui.dp(12)
 
+        ListView {
+            id: languagePairs
+            visible: count > 0
+            Layout.fillWidth: true
+            Layout.preferredHeight: ui.dp(32)
+            Layout.topMargin: ui.dp(4)
+            orientation: ListView.Horizontal
+            spacing: ui.dp(6)
+            clip: true
+            model: appBridge.language_pairs_model
+
+            delegate: Rectangle {
+                height: languagePairs.height
+                width: pairRow.implicitWidth + ui.dp(20)
+                radius: height / 2
+                color: model.current ? theme.surfaceAltColor : theme.surfaceColor
+                border.color: model.current ? theme.accentColor : theme.borderColor
+
+                MouseArea {
+                    anchors.fill: parent
+                    onClicked: appBridge.select_language_pair(model.from_code, model.to_code)
+                    onPressAndHold: appBridge.toggle_pinned_pair(model.from_code, model.to_code)
+                }
+
+                Row {
+                    id: pairRow
+                    anchors.centerIn: parent
+                    spacing: ui.dp(4)
+
+                    Image {
+                        anchors.verticalCenter: parent.verticalCenter
+                        width: ui.dp(16); height: ui.dp(16)
+                        source: appBridge.asset_url(model.pinned ? "star_filled.svg" : "star_outline.svg")
+                        sourceSize.width: ui.dp(16); sourceSize.height: ui.dp(16)
+
+                        MouseArea {
+                            anchors.fill: parent
+                            anchors.margins: -ui.dp(6)
+                            onClicked: appBridge.toggle_pinned_pair(model.from_code, model.to_code)
+                        }
+                    }
+
+                    Label {
+                        anchors.verticalCenter: parent.verticalCenter
+                        text: model.label
+                        color: theme.textPrimary
+                        font.pointSize: ui.pt(13)
+                    }
+                }
+            }
+        }
+
         ScrollView {
             id: inputScroll
             visible: !appBridge.image_mode
@@ -132,7 +189,7 @@ Item {
 
                     PlaceholderText {
                         target: inputArea
-                        placeholderText: "Enter text"
+                        placeholderText: appBridge.tr("Enter text")
                         placeholderColor: theme.textSecondary
                     }
 
@@ -188,6 +245,168 @@ Item {
                 imageMargin: ui.dp(12)
                 interactive: true
                 onImageClicked: appBridge.open_image_viewer()
+                onBlockPressAndHold: {
+                    blockEditPopup.blockIndex = index
+                    blockEditArea.text = sourceText
+                    blockEditPopup.open()
+                    blockEditArea.forceActiveFocus()
+                }
+            }
+
+            Popup {
+                id: blockEditPopup
+                property int blockIndex: -1
+                x: ui.dp(12)
+                y: ui.dp(12)
+                width: Math.max(0, parent.width - ui.dp(24))
+                height: Math.min(parent.height - ui.dp(24), blockEditColumn.implicitHeight + ui.dp(24))
+                modal: false
+                closePolicy: Popup.CloseOnEscape | Popup.CloseOnPressOutside
+                padding: ui.dp(12)
+
+                background: Rectangle {
+                    radius: ui.dp(8)
+                    color: theme.surfaceColor
+                    border.color: theme.borderColor
+                    border.width: 1
+                }
+
+                contentItem: Column {
+                    id: blockEditColumn
+                    spacing: ui.dp(8)
+
+                    Label {
+                        text: appBridge.tr("Recognized text")
+                        color: theme.textPrimary
+                        font.pointSize: ui.pt(14)
+                        font.bold: true
+                    }
+
+                    TextArea {
+                        id: blockEditArea
+                        width: parent.width
+                        wrapMode: TextEdit.Wrap
+                        selectByMouse: appBridge.desktop_mode
+                        color: theme.textPrimary
+                        font.pointSize: ui.pt(15)
+                        background: Rectangle {
+                            color: theme.backgroundColor
+                            border.color: theme.borderColor
+                            border.width: 1
+                        }
+                    }
+
+                    Row {
+                        anchors.right: parent.right
+                        spacing: ui.dp(16)
+
+                        Label {
+                            text: appBridge.tr("Cancel")
+                            color: theme.textSecondary
+                            font.pointSize: ui.pt(15)
+
+                            MouseArea {
+                                anchors.fill: parent
+                                onClicked: blockEditPopup.close()
+                            }
+                        }
+
+                        Label {
+                            text: appBridge.ocr_only ? appBridge.tr("Save") : appBridge.tr("Translate")
+                            color: theme.accentColor
+                            font.pointSize: ui.pt(15)
+
+                            MouseArea {
+                                anchors.fill: parent
+                                onClicked: {
+                                    appBridge.edit_image_block(blockEditPopup.blockIndex, blockEditArea.text)
+                                    blockEditPopup.close()
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
+            Flow {
+                anchors.left: parent.left
+                anchors.right: parent.right
+                anchors.bottom: parent.bottom
+                anchors.margins: ui.dp(12)
+                spacing: ui.dp(8)
+
+                OverlayPill {
+                    height: root.imageOverlayButtonSize
+                    visible: appBridge.page_count > 1
+                    text: "‹"
+                    onClicked: if (appBridge.current_page > 0) appBridge.show_image_page(appBridge.current_page - 1)
+                }
+
+                OverlayPill {
+                    height: root.imageOverlayButtonSize
+                    visible: appBridge.page_count > 1
+                    text: appBridge.tr("Page {page} of {count}")
+                          .replace("{page}", appBridge.current_page + 1)
+                          .replace("{count}", appBridge.page_count)
+                }
+
+                OverlayPill {
+                    height: root.imageOverlayButtonSize
+                    visible: appBridge.page_count > 1
+                    text: "›"
+                    onClicked: if (appBridge.current_page + 1 < appBridge.page_count) appBridge.show_image_page(appBridge.current_page + 1)
+                }
+
+                OverlayPill {
+                    height: root.imageOverlayButtonSize
+                    visible: appBridge.page_count > 1
+                    text: appBridge.tr("Export text")
+                    onClicked: if (imageShareLoader.item) imageShareLoader.item.exportPageTexts()
+                }
+
+                OverlayPill {
+                    height: root.imageOverlayButtonSize
+                    text: appBridge.tr("Rotate")
+                    onClicked: appBridge.rotate_image()
+                }
+
+                OverlayPill {
+                    height: root.imageOverlayButtonSize
+                    active: appBridge.picking_corners || appBridge.perspective_corrected
+                    activeColor: theme.accentColor
+                    text: appBridge.picking_corners
+                          ? appBridge.tr("Cancel")
+                          : appBridge.perspective_corrected ? appBridge.tr("Undo perspective") : appBridge.tr("Fix perspective")
+                    onClicked: {
+                        if (appBridge.picking_corners || appBridge.perspective_corrected) {
+                            appBridge.reset_perspective()
+                        } else {
+                            appBridge.start_perspective_correction()
+                        }
+                    }
+                }
+
+                OverlayPill {
+                    height: root.imageOverlayButtonSize
+                    active: appBridge.select_regions
+                    activeColor: theme.accentColor
+                    text: appBridge.select_regions ? appBridge.tr("Whole image") : appBridge.tr("Select area")
+                    onClicked: {
+                        if (appBridge.select_regions) {
+                            appBridge.clear_image_regions()
+                        } else {
+                            appBridge.set_select_regions_value(true)
+                        }
+                    }
+                }
+
+                OverlayPill {
+                    height: root.imageOverlayButtonSize
+                    active: appBridge.ocr_only
+                    activeColor: theme.accentColor
+                    text: appBridge.tr("Text only")
+                    onClicked: appBridge.set_ocr_only_value(!appBridge.ocr_only)
+                }
             }
 
             Row {
@@ -258,7 +477,7 @@ Item {
                 spacing: ui.dp(2)
 
                 Label {
-                    text: "Translate from"
+                    text: appBridge.tr("Translate from")
                     color: theme.textSecondary
                     font.pointSize: ui.pt(13)
                 }
@@ -301,6 +520,71 @@ Item {
             }
         }
 
+        Label {
+            visible: !appBridge.image_mode && appBridge.assumed_source_language_name.length > 0
+            Layout.fillWidth: true
+            Layout.bottomMargin: ui.dp(4)
+            text: appBridge.tr("Translating from {language} (detected)").replace("{language}", appBridge.assumed_source_language_name)
+            color: theme.textSecondary
+            font.pointSize: ui.pt(13)
+            elide: Text.ElideRight
+        }
+
+        Column {
+            visible: appBridge.show_detection_candidates || appBridge.mixed_languages_text.length > 0
+            Layout.fillWidth: true
+            Layout.bottomMargin: ui.dp(4)
+            spacing: ui.dp(6)
+
+            Label {
+                visible: appBridge.mixed_languages_text.length > 0
+                width: parent.width
+                text: appBridge.mixed_languages_text
+                color: theme.textSecondary
+                font.pointSize: ui.pt(13)
+                wrapMode: Text.Wrap
+            }
+
+            Flow {
+                visible: appBridge.show_detection_candidates
+                width: parent.width
+                spacing: ui.dp(6)
+
+                Label {
+                    height: ui.dp(32)
+                    verticalAlignment: Text.AlignVCenter
+                    text: appBridge.tr("Could be")
+                    color: theme.textSecondary
+                    font.pointSize: ui.pt(13)
+                }
+
+                Repeater {
+                    model: appBridge.detection_candidates_model
+
+                    delegate: Rectangle {
+                        height: ui.dp(32)
+                        width: candidateLabel.implicitWidth + ui.dp(24)
+                        radius: height / 2
+                        color: theme.surfaceColor
+                        border.color: model.installed ? theme.accentColor : theme.borderColor
+
+                        Label {
+                            id: candidateLabel
+                            anchors.centerIn: parent
+                            text: model.name + " " + model.percent + "%"
+                            color: theme.textPrimary
+                            font.pointSize: ui.pt(13)
+                        }
+
+                        MouseArea {
+                            anchors.fill: parent
+                            onClicked: appBridge.choose_detected_language(model.code)
+                        }
+                    }
+                }
+            }
+        }
+
         Rectangle {
             visible: !appBridge.show_missing_card
             Layout.fillWidth: true
@@ -344,7 +628,7 @@ Item {
                         x: ui.dp(12)
                         y: ui.dp(12)
                         width: Math.max(0, parent.width - ui.dp(24))
-                        height: outputArea.height + (outputTransliteration.visible ? ui.dp(6) + outputTransliteration.implicitHeight : 0)
+                        height: extraOutputs.y + extraOutputs.implicitHeight
 
                         TextArea {
                             id: outputArea
@@ -358,19 +642,21 @@ Item {
                             leftPadding: 0
                             rightPadding: 0
                             bottomPadding: 0
-                            text: appBridge.output_text
-                            readOnly: true
+                            text: root.editingExtractedText ? appBridge.input_text : appBridge.output_text
+                            readOnly: !root.editingExtractedText
                             wrapMode: TextEdit.Wrap
-                            activeFocusOnPress: appBridge.desktop_mode
+                            activeFocusOnPress: appBridge.desktop_mode || root.editingExtractedText
                             selectByMouse: appBridge.desktop_mode
                             color: theme.textPrimary
                             font.pointSize: ui.pt(16)
                             background: Item {}
+                            onTextChanged: if (root.editingExtractedText && text !== appBridge.input_text) appBridge.process_text(text)
                         }
 
                         MouseArea {
                             id: lookupOutputArea
                             property bool holdTriggered: false
+                            enabled: !root.editingExtractedText
                             anchors.fill: outputArea
                             acceptedButtons: Qt.LeftButton
                             pressAndHoldInterval: 450
@@ -410,13 +696,144 @@ Item {
                             color: theme.textSecondary
                             font.pointSize: ui.pt(13)
                         }
+
+                        Column {
+                            id: translationRoute
+                            property bool expanded: false
+                            visible: appBridge.translation_hop_count > 1 && appBridge.output_text.length > 0
+                            y: outputTransliteration.y + (outputTransliteration.visible ? outputTransliteration.implicitHeight + ui.dp(6) : 0)
+                            width: parent.width
+                            spacing: ui.dp(4)
+
+                            Text {
+                                width: parent.width
+                                text: (translationRoute.expanded ? "▾ " : "▸ ") + appBridge.tr("via {route}").replace("{route}", appBridge.translation_route)
+                                wrapMode: Text.Wrap
+                                color: theme.textSecondary
+                                font.pointSize: ui.pt(11)
+
+                                MouseArea {
+                                    anchors.fill: parent
+                                    onClicked: translationRoute.expanded = !translationRoute.expanded
+                                }
+                            }
+
+                            Text {
+                                visible: translationRoute.expanded && text.length > 0
+                                width: parent.width
+                                text: appBridge.translation_intermediate_text
+                                wrapMode: Text.Wrap
+                                color: theme.textSecondary
+                                font.pointSize: ui.pt(12)
+                                font.italic: true
+                            }
+
+                            Text {
+                                visible: translationRoute.expanded && appBridge.translation_timings.length > 0
+                                width: parent.width
+                                text: appBridge.translation_timings
+                                wrapMode: Text.Wrap
+                                color: theme.textSecondary
+                                font.pointSize: ui.pt(10)
+                            }
+                        }
+
+                        Column {
+                            id: extraOutputs
+                            y: translationRoute.y + (translationRoute.visible ? translationRoute.implicitHeight + ui.dp(6) : 0)
+                            width: parent.width
+                            spacing: ui.dp(10)
+
+                            Repeater {
+                                model: appBridge.image_mode ? null : appBridge.extra_outputs_model
+
+                                delegate: Column {
+                                    width: extraOutputs.width
+                                    spacing: ui.dp(4)
+
+                                    Rectangle {
+                                        width: parent.width
+                                        height: 1
+                                        color: theme.borderColor
+                                    }
+
+                                    RowLayout {
+                                        width: parent.width
+                                        spacing: ui.dp(8)
+
+                                        Label {
+                                            Layout.fillWidth: true
+                                            text: model.name
+                                            color: theme.textSecondary
+                                            font.pointSize: ui.pt(12)
+                                            font.bold: true
+                                        }
+
+                                        FeedbackIconButton {
+                                            Layout.preferredWidth: root.clipboardButtonSize
+                                            Layout.preferredHeight: root.clipboardButtonSize
+                                            iconSize: root.clipboardIconSize
+                                            iconSource: appBridge.asset_url("copy.svg")
+                                            onClicked: {
+                                                extraOutputText.selectAll()
+                                                extraOutputText.copy()
+                                                extraOutputText.deselect()
+                                            }
+                                        }
+
+                                        FeedbackIconButton {
+                                            visible: model.tts_available
+                                            Layout.preferredWidth: root.clipboardButtonSize
+                                            Layout.preferredHeight: root.clipboardButtonSize
+                                            iconSize: root.clipboardIconSize
+                                            iconSource: appBridge.asset_url("tts.svg")
+                                            onClicked: appBridge.speak_extra_output(index)
+                                        }
+                                    }
+
+                                    TextEdit {
+                                        id: extraOutputText
+                                        width: parent.width
+                                        text: model.text
+                                        readOnly: true
+                                        selectByMouse: appBridge.desktop_mode
+                                        wrapMode: TextEdit.Wrap
+                                        color: theme.textPrimary
+                                        font.pointSize: ui.pt(16)
+                                    }
+
+                                    Text {
+                                        visible: model.transliteration.length > 0
+                                        width: parent.width
+                                        text: model.transliteration
+                                        wrapMode: Text.Wrap
+                                        color: theme.textSecondary
+                                        font.pointSize: ui.pt(13)
+                                    }
+                                }
+                            }
+
+                            Label {
+                                visible: !appBridge.image_mode
+                                text: appBridge.extra_target_language_names.length > 0
+                                      ? appBridge.tr("Also into: {languages}").replace("{languages}", appBridge.extra_target_language_names.join(", "))
+                                      : appBridge.tr("+ Translate into more languages")
+                                color: theme.accentColor
+                                font.pointSize: ui.pt(11)
+
+                                MouseArea {
+                                    anchors.fill: parent
+                                    onClicked: extraTargetsPopup.open()
+                                }
+                            }
+                        }
                     }
                 }
             }
 
             FeedbackIconButton {
                 id: copyButton
-                visible: appBridge.output_text.length > 0
+                visible: outputArea.text.length > 0
                 anchors.top: parent.top
                 anchors.right: parent.right
                 anchors.topMargin: ui.dp(12)
@@ -433,6 +850,21 @@ Item {
                 }
             }
 
+            FeedbackIconButton {
+                id: translateExtractedButton
+                visible: root.editingExtractedText && appBridge.input_text.length > 0
+                anchors.top: copyButton.bottom
+                anchors.right: parent.right
+                anchors.topMargin: ui.dp(8)
+                anchors.rightMargin: ui.dp(12)
+                width: root.clipboardButtonSize
+                height: root.clipboardButtonSize
+                iconSize: root.clipboardIconSize
+                iconSource: appBridge.asset_url("translate.svg")
+
+                onClicked: appBridge.translate_extracted_text()
+            }
+
             Item {
                 id: speechButton
                 visible: (appBridge.tts_available || appBridge.tts_loading || appBridge.tts_playing)
@@ -472,6 +904,59 @@ Item {
                 }
             }
 
+            Popup {
+                id: extraTargetsPopup
+                x: ui.dp(12)
+                y: ui.dp(12)
+                width: Math.min(ui.dp(280), parent.width - ui.dp(24))
+                height: Math.min(parent.height - ui.dp(24), extraTargetsColumn.implicitHeight + ui.dp(24))
+                modal: false
+                closePolicy: Popup.CloseOnEscape | Popup.CloseOnPressOutside
+                padding: ui.dp(12)
+
+                background: Rectangle {
+                    radius: ui.dp(8)
+                    color: theme.surfaceColor
+                    border.color: theme.borderColor
+                    border.width: 1
+                }
+
+                contentItem: ScrollView {
+                    clip: true
+                    contentWidth: availableWidth
+
+                    Column {
+                        id: extraTargetsColumn
+                        width: parent.width
+                        spacing: ui.dp(4)
+
+                        Label {
+                            text: appBridge.tr("Also translate into")
+                            color: theme.textPrimary
+                            font.pointSize: ui.pt(16)
+                            font.bold: true
+                        }
+
+                        Repeater {
+                            model: appBridge.installed_to_language_codes
+
+                            delegate: DarkSwitch {
+                                readonly property string languageName: appBridge.installed_to_language_names[index] || ""
+                                visible: languageName !== appBridge.target_language_name
+                                         && languageName !== appBridge.source_language_name
+                                width: extraTargetsColumn.width
+                                height: visible ? implicitHeight : 0
+                                theme: root.theme
+                                desktopMode: appBridge.desktop_mode
+                                label: languageName
+                                checked: appBridge.extra_target_language_codes.indexOf(modelData) >= 0
+                                onToggled: appBridge.set_extra_target_language(modelData, checked)
+                            }
+                        }
+                    }
+                }
+            }
+
             Popup {
                 id: speechOptionsPopup
                 property bool voicePickerExpanded: false
@@ -504,7 +989,7 @@ Item {
                         spacing: ui.dp(12)
 
                         Label {
-                            text: "Playback speed"
+                            text: appBridge.tr("Playback speed")
                             color: theme.textPrimary
                             font.pointSize: ui.pt(16)
                             font.bold: true
@@ -570,7 +1055,7 @@ Item {
                         }
 
                         Label {
-                            text: "Voice"
+                            text: appBridge.tr("Voice")
                             color: theme.textPrimary
                             font.pointSize: ui.pt(16)
                             font.bold: true
@@ -673,13 +1158,18 @@ Item {
         icon.source: appBridge.asset_url("camera.svg")
         icon.width: ui.dp(28)
         icon.height: ui.dp(28)
-        text: "Camera"
+        text: appBridge.tr("Camera")
         background: Rectangle {
             radius: width / 2
             color: parent.down ? Qt.darker(theme.accentColor, 1.15) : theme.accentColor
             border.width: 0
         }
         onClicked: if (imagePickerLoader.item) imagePickerLoader.item.open()
+        // Holding the button picks an image for text extraction only.
+        onPressAndHold: {
+            appBridge.set_ocr_only_value(true)
+            if (imagePickerLoader.item) imagePickerLoader.item.open()
+        }
     }
 
     Rectangle {
@@ -15,7 +15,7 @@ Item {
         anchors.fill: parent
         visible: false
         showTitle: true
-        headerText: "Choose from"
+        headerText: appBridge ? appBridge.tr("Choose from") : "Choose from"
         contentType: ContentType.Pictures
         handler: ContentHandler.Source
 
@@ -27,12 +27,37 @@ Item {
         onPeerSelected: {
             visible = false
             if (peer) {
-                peer.selectionType = ContentTransfer.Single
+                peer.selectionType = ContentTransfer.Multiple
                 root.activeTransfer = peer.request()
             }
         }
     }
 
+    Connections {
+        target: ContentHub
+
+        function onImportRequested(transfer) {
+            root.openIncoming(transfer)
+        }
+
+        function onShareRequested(transfer) {
+            root.openIncoming(transfer)
+        }
+    }
+
+    function openIncoming(transfer) {
+        if (!transfer || !transfer.items || transfer.items.length === 0) {
+            return
+        }
+        const item = transfer.items[0]
+        if (item.text && item.text.length > 0) {
+            appBridge.open_launch(item.text, "")
+        } else {
+            appBridge.open_launch("", item.url.toString())
+        }
+        transfer.finalize()
+    }
+
     Connections {
         target: activeTransfer
         ignoreUnknownSignals: true
@@ -45,7 +70,11 @@ Item {
             if (activeTransfer.state === ContentTransfer.Charged &&
                     activeTransfer.items &&
                     activeTransfer.items.length > 0) {
-                appBridge.process_image_selection(activeTransfer.items[0].url.toString())
+                var urls = []
+                for (var i = 0; i < activeTransfer.items.length; i += 1) {
+                    urls.push(activeTransfer.items[i].url.toString())
+                }
+                appBridge.process_image_files(urls.join("\n"))
                 root.activeTransfer = null
             } else if (activeTransfer.state === ContentTransfer.Aborted ||
                        activeTransfer.state === ContentTransfer.Finalized) {
@@ -7,6 +7,7 @@ Item {
     property string pendingUrl: ""
     property var activeTransfer: null
     property var sharedItem: null
+    property int pendingContentType: ContentType.Pictures
 
     function share(url) {
         if (!url) {
@@ -17,9 +18,18 @@ Item {
         if (!pendingUrl.length) {
             return
         }
+        pendingContentType = ContentType.Pictures
         picker.visible = true
     }
 
+    function exportPageTexts() {
+        pendingUrl = appBridge.export_page_texts("")
+        if (pendingUrl.length) {
+            pendingContentType = ContentType.Documents
+            picker.visible = true
+        }
+    }
+
     function destroySharedItem() {
         if (sharedItem) {
             sharedItem.destroy()
@@ -44,7 +54,7 @@ Item {
         anchors.fill: parent
         visible: false
         showTitle: false
-        contentType: ContentType.Pictures
+        contentType: root.pendingContentType
         handler: ContentHandler.Share
         onPeerSelected: {
             visible = false
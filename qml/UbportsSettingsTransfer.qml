@@ -0,0 +1,106 @@
+import QtQuick 2.15
+import Lomiri.Content 1.1
+
+Item {
+    id: root
+    property var appBridge
+    property string pendingUrl: ""
+    property var activeTransfer: null
+    property var sharedItem: null
+
+    function exportSettings(withGlossary) {
+        pendingUrl = appBridge.export_settings("", withGlossary)
+        if (pendingUrl.length) {
+            sharePicker.visible = true
+        }
+    }
+
+    function importSettings() {
+        importPicker.visible = true
+    }
+
+    function cleanupTransfer() {
+        activeTransfer = null
+        pendingUrl = ""
+        if (sharedItem) {
+            sharedItem.destroy()
+            sharedItem = null
+        }
+    }
+
+    ContentPeerPicker {
+        id: sharePicker
+        anchors.fill: parent
+        visible: false
+        showTitle: false
+        contentType: ContentType.Documents
+        handler: ContentHandler.Share
+
+        onPeerSelected: {
+            visible = false
+            sharedItem = shareItemComponent.createObject(root, { "url": pendingUrl })
+            peer.selectionType = ContentTransfer.Single
+            activeTransfer = peer.request()
+            if (!sharedItem || !activeTransfer) {
+                cleanupTransfer()
+                return
+            }
+            activeTransfer.items = [sharedItem]
+            activeTransfer.state = ContentTransfer.Charged
+        }
+        onCancelPressed: {
+            visible = false
+            cleanupTransfer()
+        }
+    }
+
+    ContentPeerPicker {
+        id: importPicker
+        anchors.fill: parent
+        visible: false
+        showTitle: true
+        headerText: appBridge ? appBridge.tr("Choose from") : "Choose from"
+        contentType: ContentType.Documents
+        handler: ContentHandler.Source
+
+        onPeerSelected: {
+            visible = false
+            if (peer) {
+                peer.selectionType = ContentTransfer.Single
+                root.activeTransfer = peer.request()
+            }
+        }
+        onCancelPressed: {
+            visible = false
+            root.activeTransfer = null
+        }
+    }
+
+    Component {
+        id: shareItemComponent
+
+        ContentItem {}
+    }
+
+    Connections {
+        target: activeTransfer
+        ignoreUnknownSignals: true
+
+        function onStateChanged() {
+            if (!activeTransfer) {
+                return
+            }
+
+            if (!pendingUrl.length &&
+                    activeTransfer.state === ContentTransfer.Charged &&
+                    activeTransfer.items &&
+                    activeTransfer.items.length > 0) {
+                appBridge.import_settings(activeTransfer.items[0].url.toString())
+                cleanupTransfer()
+            } else if (activeTransfer.state === ContentTransfer.Aborted ||
+                       activeTransfer.state === ContentTransfer.Finalized) {
+                cleanupTransfer()
+            }
+        }
+    }
+}
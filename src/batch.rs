@@ -0,0 +1,260 @@
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuilder};
+use translator::TranslatorSession;
+
+use crate::translation::{
+    self, TranslationError, TranslationOptions, TranslationOutput, TranslationRoute,
+    TranslationTarget,
+};
+
+/// Spreads independent segments over a dedicated worker pool. `TranslatorSession`
+/// is shared across threads elsewhere (TTS, the event loop), so segments only
+/// need a shared reference to it.
+pub struct BatchTranslator {
+    /// `None` runs on rayon's global pool.
+    pool: Option<ThreadPool>,
+}
+
+impl BatchTranslator {
+    /// `workers == 0` uses one worker per available core.
+    pub fn new(workers: usize) -> Result<Self, String> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(workers)
+            .thread_name(|index| format!("batch-translate-{index}"))
+            .build()
+            .map_err(|err| format!("Failed to start translation workers: {err}"))?;
+        Ok(Self { pool: Some(pool) })
+    }
+
+    /// Shares rayon's global pool, for when a dedicated one can't be started.
+    pub fn global() -> Self {
+        Self { pool: None }
+    }
+
+    pub fn workers(&self) -> usize {
+        self.pool
+            .as_ref()
+            .map_or_else(rayon::current_num_threads, ThreadPool::current_num_threads)
+    }
+
+    fn install<R: Send>(&self, work: impl FnOnce() -> R + Send) -> R {
+        match &self.pool {
+            Some(pool) => pool.install(work),
+            None => work(),
+        }
+    }
+
+    pub fn translate_segments(
+        &self,
+        session: &TranslatorSession,
+        from: &str,
+        to: &str,
+        segments: &[&str],
+        options: &TranslationOptions,
+    ) -> Vec<Result<TranslationOutput, TranslationError>> {
+        self.install(|| {
+            segments
+                .par_iter()
+                .map(|segment| translation::translate_text(session, from, to, segment, options))
+                .collect()
+        })
+    }
+
+    /// Translates `text` into every target, running the per-target hops on
+    /// this pool.
+    pub fn translate_multi(
+        &self,
+        session: &TranslatorSession,
+        from: &str,
+        text: &str,
+        targets: &[TranslationTarget],
+    ) -> Vec<Result<TranslationOutput, TranslationError>> {
+        self.install(|| translation::translate_text_multi(session, from, text, targets))
+    }
+
+    /// Translates each paragraph of `text` independently and stitches the
+    /// results back together with the original separators.
+    pub fn translate_document(
+        &self,
+        session: &TranslatorSession,
+        from: &str,
+        to: &str,
+        text: &str,
+        options: &TranslationOptions,
+    ) -> Result<TranslationOutput, TranslationError> {
+        let pieces = split_paragraphs(text);
+        if pieces.len() <= 1 {
+            return translation::translate_text(session, from, to, text, options);
+        }
+
+        let segments = pieces
+            .iter()
+            .map(|(segment, _)| *segment)
+            .collect::<Vec<_>>();
+        let results = self.translate_segments(session, from, to, &segments, options);
+
+        let mut output = String::new();
+        let mut route = TranslationRoute::default();
+        let mut intermediate = String::new();
+        for ((_, separator), result) in pieces.iter().zip(results) {
+            let translated = result?;
+            output.push_str(&translated.text);
+            output.push_str(separator);
+            if let Some(text) = &translated.route.intermediate_text {
+                intermediate.push_str(text);
+                intermediate.push_str(separator);
+            }
+            merge_route(&mut route, translated.route);
+        }
+        if !intermediate.is_empty() {
+            route.intermediate_text = Some(intermediate);
+        }
+
+        Ok(TranslationOutput {
+            text: output,
+            route,
+        })
+    }
+}
+
+/// Splits on blank lines (with `\n` or `\r\n` endings), keeping each
+/// paragraph's trailing separator so the document can be reassembled verbatim.
+fn split_paragraphs(text: &str) -> Vec<(&str, &str)> {
+    let mut pieces = Vec::new();
+    let mut segment_start = 0;
+    let mut search_start = 0;
+    while let Some(found) = text[search_start..].find('\n') {
+        let newline = search_start + found;
+        search_start = newline + 1;
+        let blank_line_follows = text[search_start..]
+            .trim_start_matches([' ', '\t', '\r'])
+            .starts_with('\n');
+        if !blank_line_follows {
+            continue;
+        }
+
+        let separator_start = text[..newline].trim_end_matches('\r').len();
+        let separator_end = text[newline..]
+            .find(|ch: char| !ch.is_whitespace())
+            .map_or(text.len(), |offset| newline + offset);
+        pieces.push((
+            &text[segment_start..separator_start],
+            &text[separator_start..separator_end],
+        ));
+        segment_start = separator_end;
+        search_start = separator_end;
+    }
+    if segment_start < text.len() {
+        pieces.push((&text[segment_start..], ""));
+    }
+    pieces
+}
+
+/// Sums per-hop time so the route reports total work per hop.
+fn merge_route(total: &mut TranslationRoute, route: TranslationRoute) {
+    if total.hops.is_empty() {
+        total.hops = route.hops;
+        return;
+    }
+    for (total_hop, hop) in total.hops.iter_mut().zip(route.hops) {
+        total_hop.elapsed += hop.elapsed;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::*;
+
+    #[test]
+    fn splits_paragraphs_keeping_separators() {
+        let text = "First one.\n\nSecond\nline.\n\n\n\nThird.";
+        let pieces = split_paragraphs(text);
+        assert_eq!(
+            pieces,
+            vec![
+                ("First one.", "\n\n"),
+                ("Second\nline.", "\n\n\n\n"),
+                ("Third.", ""),
+            ]
+        );
+        let rebuilt = pieces
+            .iter()
+            .map(|(segment, separator)| format!("{segment}{separator}"))
+            .collect::<String>();
+        assert_eq!(rebuilt, text);
+
+        assert_eq!(
+            split_paragraphs("One.\r\nStill one.\r\n\r\nTwo.\r\n"),
+            vec![("One.\r\nStill one.", "\r\n\r\n"), ("Two.\r\n", "")]
+        );
+    }
+
+    /// Compares serial and parallel throughput on the bundled corpus, using
+    /// the language packs installed for the app. Run it with `bench.sh`;
+    /// `BENCH_PAIR` (default `en-nl`) must be installed and `BENCH_WORKERS`
+    /// defaults to one per core.
+    #[test]
+    #[ignore]
+    fn batch_benchmark() {
+        let pair = std::env::var("BENCH_PAIR").unwrap_or_else(|_| "en-nl".to_string());
+        let (from, to) = pair
+            .split_once('-')
+            .unwrap_or_else(|| panic!("expected BENCH_PAIR=<from>-<to>, got '{pair}'"));
+        let workers = std::env::var("BENCH_WORKERS")
+            .map(|value| value.parse::<usize>().expect("invalid BENCH_WORKERS"))
+            .unwrap_or(0);
+
+        let session = TranslatorSession::from_catalog(
+            crate::catalog_state::bundled_catalog(),
+            crate::get_app_paths().data,
+        );
+        session.refresh_snapshot();
+
+        let segments = include_str!("../data/bench_corpus.txt")
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        let options = TranslationOptions::default();
+        let serial = BatchTranslator::new(1).unwrap();
+        let parallel = BatchTranslator::new(workers).unwrap();
+
+        // Warm up model loading so neither run pays for it.
+        translation::translate_text(&session, from, to, segments[0], &options).unwrap();
+
+        let time_batch = |batch: &BatchTranslator| -> Duration {
+            let start = Instant::now();
+            let failures = batch
+                .translate_segments(&session, from, to, &segments, &options)
+                .into_iter()
+                .filter(Result::is_err)
+                .count();
+            assert_eq!(failures, 0, "{failures} segment(s) failed");
+            start.elapsed()
+        };
+        let serial_elapsed = time_batch(&serial);
+        let parallel_elapsed = time_batch(&parallel);
+        let chars = segments
+            .iter()
+            .map(|segment| segment.chars().count())
+            .sum::<usize>();
+
+        for (label, workers, elapsed) in [
+            ("serial", serial.workers(), serial_elapsed),
+            ("parallel", parallel.workers(), parallel_elapsed),
+        ] {
+            println!(
+                "batch benchmark {from}->{to} {label} workers={workers} segments={} elapsed={elapsed:?} segments/s={:.1} chars/s={:.0}",
+                segments.len(),
+                segments.len() as f64 / elapsed.as_secs_f64(),
+                chars as f64 / elapsed.as_secs_f64(),
+            );
+        }
+        println!(
+            "batch benchmark speedup {:.2}x",
+            serial_elapsed.as_secs_f64() / parallel_elapsed.as_secs_f64()
+        );
+    }
+}
@@ -68,6 +68,7 @@ pub fn languages_from_overview(overview: Vec<LanguageOverview>) -> Vec<Language>
             let built_in = library_language.is_english();
             Language {
                 code,
+                english_name: library_language.display_name.clone(),
                 name: library_language.display_name,
                 script: library_language.script,
                 dictionary_code: library_language.dictionary_code,
@@ -91,6 +92,18 @@ pub fn languages_from_overview(overview: Vec<LanguageOverview>) -> Vec<Language>
     languages
 }
 
+/// Codes of the languages that can be translated from right now.
+pub fn installed_source_codes(languages: &[Language]) -> Vec<String> {
+    languages
+        .iter()
+        .filter(|language| {
+            (language.core_installed || language.built_in)
+                && matches!(language.direction, Direction::FromOnly | Direction::Both)
+        })
+        .map(|language| language.code.clone())
+        .collect()
+}
+
 pub fn format_size(size_bytes: u64) -> String {
     const ONE_KB: u64 = 1024;
     const ONE_MB: u64 = 1024 * 1024;
@@ -0,0 +1,133 @@
+use std::path::Path;
+
+/// What the app was asked to open at launch, either from argv or forwarded
+/// from a second instance.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LaunchRequest {
+    pub text: Option<String>,
+    pub from: Option<String>,
+    pub to: Option<String>,
+    /// Local path or URL of an image to translate.
+    pub file: Option<String>,
+}
+
+impl LaunchRequest {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Turns a plain path into an absolute `file://` URL so it stays valid when
+    /// forwarded to an instance with a different working directory.
+    pub fn normalize_file(&mut self) {
+        if let Some(file) = &self.file
+            && !file.contains("://")
+        {
+            let path = std::path::absolute(file).unwrap_or_else(|_| file.into());
+            self.file = Some(file_url(&path));
+        }
+    }
+}
+
+/// Percent-encodes everything but unreserved characters and `/`, matching
+/// the decoding in `image_ocr::resolve_local_path`.
+fn file_url(path: &Path) -> String {
+    let mut url = String::from("file://");
+    for &byte in path.as_os_str().as_encoded_bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+            url.push(byte as char);
+        } else {
+            url.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    url
+}
+
+pub const USAGE: &str =
+    "Usage: offline-translator-linux [--text TEXT] [--from LANG] [--to LANG] [FILE]";
+
+/// Parses `--text`, `--from`, `--to` (also as `--flag=value`) and an optional
+/// file path or `file://` URL. Unknown flags are ignored so toolkit options
+/// still pass through.
+pub fn parse_args(args: impl IntoIterator<Item = String>) -> Result<LaunchRequest, String> {
+    let mut request = LaunchRequest::default();
+    let mut args = args.into_iter();
+
+    while let Some(arg) = args.next() {
+        let (flag, inline_value) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => {
+                (flag.to_string(), Some(value.to_string()))
+            }
+            _ => (arg.clone(), None),
+        };
+        let slot = match flag.as_str() {
+            "--text" => &mut request.text,
+            "--from" => &mut request.from,
+            "--to" => &mut request.to,
+            "--" => {
+                if let Some(file) = args.next() {
+                    request.file = Some(file);
+                }
+                continue;
+            }
+            _ if flag.starts_with('-') => {
+                eprintln!("ignoring unknown argument '{arg}'");
+                continue;
+            }
+            _ => {
+                request.file = Some(arg);
+                continue;
+            }
+        };
+        let value = inline_value
+            .or_else(|| args.next())
+            .ok_or_else(|| format!("{flag} needs a value\n{USAGE}"))?;
+        *slot = Some(value);
+    }
+
+    Ok(request)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_text_languages_and_file() {
+        assert_eq!(
+            parse_args(args(&["--text", "Guten Tag", "--from=de", "--to", "en"])).unwrap(),
+            LaunchRequest {
+                text: Some("Guten Tag".to_string()),
+                from: Some("de".to_string()),
+                to: Some("en".to_string()),
+                file: None,
+            }
+        );
+        assert_eq!(
+            parse_args(args(&["--verbose", "file:///tmp/photo.jpg"]))
+                .unwrap()
+                .file
+                .as_deref(),
+            Some("file:///tmp/photo.jpg")
+        );
+        assert!(parse_args(args(&["--to"])).is_err());
+    }
+
+    #[test]
+    fn normalized_file_urls_round_trip() {
+        let mut request = LaunchRequest {
+            file: Some("/tmp/50% off #1 ü.png".to_string()),
+            ..LaunchRequest::default()
+        };
+        request.normalize_file();
+        let url = request.file.unwrap();
+        assert_eq!(url, "file:///tmp/50%25%20off%20%231%20%C3%BC.png");
+        assert_eq!(
+            crate::image_ocr::resolve_local_path(&url).unwrap(),
+            Path::new("/tmp/50% off #1 ü.png")
+        );
+    }
+}
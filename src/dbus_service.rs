@@ -0,0 +1,147 @@
+use std::sync::Arc;
+use std::sync::mpsc::{self, Sender};
+use std::time::Duration;
+
+use zbus::{blocking, fdo, interface};
+
+use crate::cli::LaunchRequest;
+use crate::ui::UiCallbacks;
+use crate::{APP_NAME, IoEvent};
+
+pub const OBJECT_PATH: &str = "/dev/davidv/translator";
+const REPLY_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Session-bus front end. Every call is forwarded to the event loop as an
+/// `IoEvent`; methods that return data wait for the event loop's reply.
+struct TranslatorService {
+    bus_tx: Sender<IoEvent>,
+    present_text: Arc<dyn Fn(String) + Send + Sync>,
+    translate_clipboard: Arc<dyn Fn() + Send + Sync>,
+    speak_text: Arc<dyn Fn(String, String) + Send + Sync>,
+    open_launch_request: Arc<dyn Fn(LaunchRequest) + Send + Sync>,
+}
+
+impl TranslatorService {
+    fn request<T>(&self, event: impl FnOnce(Sender<T>) -> IoEvent) -> fdo::Result<T> {
+        let (reply_tx, reply_rx) = mpsc::channel();
+        self.bus_tx
+            .send(event(reply_tx))
+            .map_err(|_| fdo::Error::Failed("Translator is shutting down".to_string()))?;
+        reply_rx
+            .recv_timeout(REPLY_TIMEOUT)
+            .map_err(|_| fdo::Error::Failed("Translator did not reply".to_string()))
+    }
+}
+
+#[interface(name = "dev.davidv.translator")]
+impl TranslatorService {
+    /// `from` may be "auto" to detect the source language.
+    fn translate(&self, text: String, from: String, to: String) -> fdo::Result<String> {
+        self.request(|reply| IoEvent::ServiceTranslate {
+            text,
+            from,
+            to,
+            reply,
+        })?
+        .map_err(fdo::Error::Failed)
+    }
+
+    /// Returns the detected language code, or an empty string.
+    fn detect_language(&self, text: String) -> fdo::Result<String> {
+        self.request(|reply| IoEvent::ServiceDetectLanguage { text, reply })
+    }
+
+    /// Uses the playback speed and voice chosen in the app.
+    fn speak(&self, text: String, lang: String) {
+        (self.speak_text)(lang, text);
+    }
+
+    /// Installed languages as (code, name) pairs.
+    fn list_languages(&self) -> fdo::Result<Vec<(String, String)>> {
+        self.request(|reply| IoEvent::ServiceListLanguages { reply })
+    }
+
+    /// Raises the window, translating `text` when it is not empty.
+    fn show(&self, text: String) {
+        (self.present_text)(text);
+    }
+
+    /// Opens a launch request forwarded by a second instance. Empty strings
+    /// mean "not given".
+    fn open(&self, text: String, from: String, to: String, file: String) {
+        let given = |value: String| (!value.is_empty()).then_some(value);
+        (self.open_launch_request)(LaunchRequest {
+            text: given(text),
+            from: given(from),
+            to: given(to),
+            file: given(file),
+        });
+    }
+
+    /// Raises the window and translates the clipboard, or the primary
+    /// selection when that is enabled in settings. Meant for a global shortcut.
+    fn translate_clipboard(&self) {
+        (self.translate_clipboard)();
+    }
+}
+
+/// Claims `dev.davidv.translator` on the session bus. Fails when another
+/// instance already owns the name.
+pub fn start_dbus_service(
+    bus_tx: Sender<IoEvent>,
+    ui: &UiCallbacks,
+) -> Result<blocking::Connection, String> {
+    let service = TranslatorService {
+        bus_tx,
+        present_text: ui.present_text.clone(),
+        translate_clipboard: ui.translate_clipboard.clone(),
+        speak_text: ui.speak_text.clone(),
+        open_launch_request: ui.open_launch_request.clone(),
+    };
+    blocking::connection::Builder::session()
+        .and_then(|builder| builder.name(APP_NAME))
+        .and_then(|builder| builder.serve_at(OBJECT_PATH, service))
+        .and_then(|builder| builder.build())
+        .map_err(|err| format!("Failed to register {APP_NAME} on the session bus: {err}"))
+}
+
+/// Hands `request` to an already running instance. Returns false when there is
+/// none, in which case this process should start normally.
+pub fn forward_to_running_instance(request: &LaunchRequest) -> bool {
+    let Ok(connection) = blocking::Connection::session() else {
+        return false;
+    };
+    let has_owner = blocking::fdo::DBusProxy::new(&connection)
+        .and_then(|proxy| {
+            proxy
+                .name_has_owner(APP_NAME.try_into()?)
+                .map_err(Into::into)
+        })
+        .unwrap_or(false);
+    if !has_owner {
+        return false;
+    }
+
+    let field = |value: &Option<String>| value.clone().unwrap_or_default();
+    match connection.call_method(
+        Some(APP_NAME),
+        OBJECT_PATH,
+        Some(APP_NAME),
+        "Open",
+        &(
+            field(&request.text),
+            field(&request.from),
+            field(&request.to),
+            field(&request.file),
+        ),
+    ) {
+        Ok(_) => {
+            println!("forwarded launch request to the running instance");
+            true
+        }
+        Err(err) => {
+            eprintln!("Failed to reach the running instance: {err}");
+            false
+        }
+    }
+}
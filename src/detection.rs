@@ -0,0 +1,163 @@
+use cld2::{Format, Hints, detect_language_ext};
+
+/// Extra percentage points given to installed languages when ranking guesses.
+const INSTALLED_PRIOR: u32 = 15;
+/// Paragraphs shorter than this are too short for a per-paragraph guess.
+const MIN_PARAGRAPH_CHARS: usize = 24;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct DetectionCandidate {
+    pub code: String,
+    pub percent: u8,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct LanguageDetection {
+    /// Best guesses first, installed languages favoured.
+    pub candidates: Vec<DetectionCandidate>,
+    pub reliable: bool,
+    /// Distinct languages of the individual paragraphs, in order of appearance.
+    pub paragraph_codes: Vec<String>,
+}
+
+impl LanguageDetection {
+    /// The top candidate, when cld2 was confident about it.
+    pub fn best_code(&self) -> Option<&str> {
+        self.candidates
+            .first()
+            .filter(|_| self.reliable)
+            .map(|candidate| candidate.code.as_str())
+    }
+
+    /// The top candidate as a source to translate from: cld2 was confident
+    /// about it and it is installed.
+    pub fn installed_source(&self, installed_codes: &[String]) -> Option<&DetectionCandidate> {
+        self.candidates
+            .first()
+            .filter(|candidate| self.reliable && installed_codes.contains(&candidate.code))
+    }
+
+    pub fn is_mixed(&self) -> bool {
+        self.paragraph_codes.len() > 1
+    }
+}
+
+pub fn detect(text: &str, installed_codes: &[String]) -> LanguageDetection {
+    let (scores, reliable) = detect_scores(text, installed_codes);
+    let mut paragraph_codes = Vec::new();
+    let paragraphs = paragraphs(text)
+        .into_iter()
+        .filter(|paragraph| paragraph.chars().count() >= MIN_PARAGRAPH_CHARS)
+        .collect::<Vec<_>>();
+    if paragraphs.len() > 1 {
+        for paragraph in paragraphs {
+            let (scores, reliable) = detect_scores(&paragraph, installed_codes);
+            if let Some((code, _)) = scores.into_iter().next().filter(|_| reliable)
+                && !paragraph_codes.contains(&code)
+            {
+                paragraph_codes.push(code);
+            }
+        }
+    }
+
+    LanguageDetection {
+        candidates: rank_candidates(scores, installed_codes),
+        reliable,
+        paragraph_codes,
+    }
+}
+
+/// Runs of non-blank lines, joined with spaces so wrapped lines count as one
+/// paragraph.
+fn paragraphs(text: &str) -> Vec<String> {
+    let mut paragraphs = Vec::new();
+    let mut current = Vec::new();
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() {
+            if !current.is_empty() {
+                paragraphs.push(current.join(" "));
+                current.clear();
+            }
+        } else {
+            current.push(line);
+        }
+    }
+    if !current.is_empty() {
+        paragraphs.push(current.join(" "));
+    }
+    paragraphs
+}
+
+fn detect_scores(text: &str, installed_codes: &[String]) -> (Vec<(String, u8)>, bool) {
+    let collect = |hints: &Hints| {
+        let result = detect_language_ext(text, Format::Text, hints);
+        let scores = result
+            .scores
+            .iter()
+            .filter_map(|score| {
+                let language = score.language.as_ref()?;
+                (score.percent > 0).then(|| (language.0.to_string(), score.percent as u8))
+            })
+            .collect::<Vec<_>>();
+        (scores, result.reliability == cld2::Reliable)
+    };
+
+    let (scores, reliable) = collect(&Hints::default());
+    if reliable || installed_codes.is_empty() {
+        return (scores, reliable);
+    }
+    // Short or ambiguous text: let cld2 lean towards what the user has installed.
+    let content_language = installed_codes.join(",");
+    collect(&Hints {
+        content_language: Some(&content_language),
+        ..Default::default()
+    })
+}
+
+pub(crate) fn rank_candidates(
+    scores: Vec<(String, u8)>,
+    installed_codes: &[String],
+) -> Vec<DetectionCandidate> {
+    let mut candidates = scores
+        .into_iter()
+        .filter(|(code, percent)| *percent > 0 && code != "un")
+        .map(|(code, percent)| DetectionCandidate { code, percent })
+        .collect::<Vec<_>>();
+    let weight = |candidate: &DetectionCandidate| {
+        let prior = if installed_codes.contains(&candidate.code) {
+            INSTALLED_PRIOR
+        } else {
+            0
+        };
+        candidate.percent as u32 + prior
+    };
+    candidates.sort_by_key(|candidate| std::cmp::Reverse(weight(candidate)));
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn installed_languages_outrank_close_guesses() {
+        let scores = vec![
+            ("nl".to_string(), 45),
+            ("de".to_string(), 40),
+            ("un".to_string(), 10),
+            ("sv".to_string(), 0),
+        ];
+        let ranked = rank_candidates(scores, &["de".to_string()]);
+        let codes = ranked
+            .iter()
+            .map(|candidate| candidate.code.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(codes, ["de", "nl"]);
+        assert_eq!(ranked[0].percent, 40);
+
+        assert_eq!(
+            paragraphs("One line\nwrapped.\r\n  \r\nTwo.\n\n\n"),
+            ["One line wrapped.", "Two."]
+        );
+    }
+}
@@ -1,23 +1,125 @@
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::mpsc::Receiver;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::{Arc, Mutex, RwLock};
 use std::thread;
 use std::time::{Duration, Instant};
 
-use cld2::{Format, detect_language};
 use translator::TranslatorSession;
 
-use crate::catalog_state::languages_from_overview;
+use crate::batch::BatchTranslator;
+use crate::catalog_state::{installed_source_codes, languages_from_overview};
+use crate::detection::{self, LanguageDetection};
 use crate::download;
+use crate::http_api::ApiServer;
+use crate::i18n::{tr, tr_args};
 use crate::image_ocr;
-use crate::model::FeatureKind;
+use crate::model::{FeatureKind, OcrBackgroundMode, OcrReadingOrder};
+use crate::reading_order::{Rect, horizontal_rows};
 use crate::rendered_image_item::qimage_from_rgba_bytes;
+use crate::translation::{self, TranslationRoute, TranslationSettings};
 use crate::tts;
 use crate::ui::{ImageOverlayListItem, TtsVoiceListItem, UiCallbacks, argb_to_qml_color};
 use crate::{AppPaths, IoEvent};
 
+/// Pages of the last PDF or multi-page TIFF live here, under the data
+/// directory, until the next one is opened.
+const DOCUMENT_PAGES_DIR: &str = "document-pages";
+/// Niceness of the thread reading pages that aren't shown.
+const BACKGROUND_PAGES_NICE: i32 = 10;
+
+/// Results of the open document's pages, so turning back to a page doesn't
+/// read it again. Cleared when a newer document generation arrives.
+#[derive(Default)]
+struct PageCache {
+    generation: u64,
+    pages: HashMap<usize, (PageRead, image_ocr::ImageTranslation)>,
+}
+
+/// OCR settings a page was read with; changing any of them reads it again.
+#[derive(Clone, PartialEq)]
+struct PageRead {
+    image_path: String,
+    from: String,
+    to: String,
+    min_confidence: u32,
+    max_image_size: u32,
+    background_mode: OcrBackgroundMode,
+    reading_order: OcrReadingOrder,
+    ocr_only: bool,
+}
+
+impl PageCache {
+    fn get(
+        &self,
+        generation: u64,
+        page: usize,
+        read: &PageRead,
+    ) -> Option<image_ocr::ImageTranslation> {
+        if self.generation != generation {
+            return None;
+        }
+        self.pages
+            .get(&page)
+            .filter(|(cached, _)| cached == read)
+            .map(|(_, result)| result.clone())
+    }
+
+    fn insert(
+        &mut self,
+        generation: u64,
+        page: usize,
+        read: PageRead,
+        result: &image_ocr::ImageTranslation,
+    ) {
+        if generation > self.generation {
+            self.generation = generation;
+            self.pages.clear();
+        }
+        if generation == self.generation {
+            self.pages.insert(page, (read, result.clone()));
+        }
+    }
+}
+
+type BackgroundJob = Box<dyn FnOnce() + Send>;
+
+/// Runs jobs one at a time on a thread with lowered priority, so reading
+/// pages in the background doesn't slow down the page being shown.
+fn spawn_background_worker() -> Sender<BackgroundJob> {
+    let (jobs, job_rx) = mpsc::channel::<BackgroundJob>();
+    let spawned = thread::Builder::new()
+        .name("background-pages".to_string())
+        .spawn(move || {
+            // Linux keeps a nice value per thread, so this only affects this one.
+            if unsafe { libc::setpriority(libc::PRIO_PROCESS, 0, BACKGROUND_PAGES_NICE) } != 0 {
+                eprintln!(
+                    "background pages: can't lower priority: {}",
+                    std::io::Error::last_os_error()
+                );
+            }
+            for job in job_rx {
+                job();
+            }
+        });
+    if let Err(err) = spawned {
+        eprintln!("Can't start background pages thread: {err}");
+    }
+    jobs
+}
+
 pub fn run_eventloop(bus_rx: Receiver<IoEvent>, ui: UiCallbacks, session: Arc<TranslatorSession>) {
     let mut app_paths = None::<AppPaths>;
+    let batch = Arc::new(RwLock::new(BatchTranslator::new(0).unwrap_or_else(|err| {
+        eprintln!("{err}");
+        BatchTranslator::global()
+    })));
+    let mut api_server = None::<ApiServer>;
+    let translation_settings = Arc::new(RwLock::new(TranslationSettings::default()));
+    let mut installed_codes = Vec::<String>::new();
+    let page_cache = Arc::new(Mutex::new(PageCache::default()));
+    let latest_generation = Arc::new(AtomicU64::new(0));
+    let background_pages = spawn_background_worker();
 
     while let Ok(msg) = bus_rx.recv() {
         match msg {
@@ -29,9 +131,51 @@ pub fn run_eventloop(bus_rx: Receiver<IoEvent>, ui: UiCallbacks, session: Arc<Tr
                 std::fs::create_dir_all(&path.config).expect("can't make config dir");
 
                 session.refresh_snapshot();
-                (ui.set_languages)(languages_from_overview(session.language_overview()));
+                installed_codes = publish_languages(&session, &ui);
                 println!("Load took {:?}", load_start.elapsed());
             }
+            IoEvent::SetBatchWorkers(workers) => match BatchTranslator::new(workers) {
+                Ok(new_batch) => {
+                    println!("batch translation workers: {}", new_batch.workers());
+                    *batch.write().unwrap() = new_batch;
+                }
+                Err(err) => eprintln!("{err}"),
+            },
+            IoEvent::SetTranslationSettings(settings) => {
+                *translation_settings.write().unwrap() = settings;
+            }
+            IoEvent::SetApiServer { enabled, port } => {
+                if api_server
+                    .as_ref()
+                    .is_some_and(|server| enabled && server.port() == port)
+                {
+                    continue;
+                }
+                api_server = None;
+                let status = if enabled {
+                    match ApiServer::start(
+                        port,
+                        Arc::clone(&session),
+                        Arc::clone(&batch),
+                        Arc::clone(&translation_settings),
+                    ) {
+                        Ok(server) => {
+                            api_server = Some(server);
+                            tr_args(
+                                "Listening on http://127.0.0.1:{port}",
+                                &[("port", &port.to_string())],
+                            )
+                        }
+                        Err(err) => {
+                            eprintln!("http api: {err}");
+                            err
+                        }
+                    }
+                } else {
+                    String::new()
+                };
+                (ui.set_api_server_status)(status);
+            }
             IoEvent::DownloadRequest {
                 code,
                 feature,
@@ -50,32 +194,106 @@ pub fn run_eventloop(bus_rx: Receiver<IoEvent>, ui: UiCallbacks, session: Arc<Tr
                 }
 
                 session.refresh_snapshot();
-                (ui.set_languages)(languages_from_overview(session.language_overview()));
+                installed_codes = publish_languages(&session, &ui);
             }
             IoEvent::DeleteLanguage { code, feature } => {
                 let delete_plan = session.prepare_delete(&code, feature.into());
                 session.apply_delete_plan(&delete_plan);
-                (ui.set_languages)(languages_from_overview(session.language_overview()));
+                installed_codes = publish_languages(&session, &ui);
             }
-            IoEvent::TranslationRequest { text, from, to } => {
-                send_detection_to_ui(&text, &ui);
+            IoEvent::TranslationRequest {
+                text,
+                from,
+                to,
+                auto_source,
+                mut options,
+            } => {
+                let detection = send_detection_to_ui(&text, &installed_codes, &ui);
+                let assumed = assumed_source(
+                    &detection,
+                    auto_source,
+                    &from,
+                    std::slice::from_ref(&to),
+                    &installed_codes,
+                );
+                (ui.set_assumed_source_language)(assumed.clone().unwrap_or_default());
+                let from = match assumed {
+                    Some(code) => {
+                        // Glossary terms come from the detected pair, not the selected one.
+                        options = translation_settings.read().unwrap().options(&code, &to);
+                        code
+                    }
+                    None => from,
+                };
 
                 let start = Instant::now();
 
-                let result = session.translate_text(&from, &to, &text).map_err(|error| {
-                    if error.is_missing_asset() {
-                        format!("Missing installed language pair {from}->{to}")
-                    } else {
-                        error.message
-                    }
-                });
+                let result = batch
+                    .read()
+                    .unwrap()
+                    .translate_document(&session, &from, &to, &text, &options);
 
-                let text = match result {
-                    Ok(result) => result,
-                    Err(message) => message,
+                let (text, route) = match result {
+                    Ok(output) => (output.text, output.route),
+                    Err(err) => (err.localized(), TranslationRoute::default()),
                 };
                 println!("translation took {:?} = '{}'", start.elapsed(), text);
                 (ui.set_output_text)(text);
+                (ui.set_translation_route)(route);
+            }
+            IoEvent::MultiTranslationRequest {
+                text,
+                from,
+                auto_source,
+                mut targets,
+            } => {
+                let detection = send_detection_to_ui(&text, &installed_codes, &ui);
+                let target_codes = targets
+                    .iter()
+                    .map(|target| target.to.clone())
+                    .collect::<Vec<_>>();
+                let assumed = assumed_source(
+                    &detection,
+                    auto_source,
+                    &from,
+                    &target_codes,
+                    &installed_codes,
+                );
+                (ui.set_assumed_source_language)(assumed.clone().unwrap_or_default());
+                let from = match assumed {
+                    Some(code) => {
+                        let settings = translation_settings.read().unwrap();
+                        for target in &mut targets {
+                            target.options = settings.options(&code, &target.to);
+                        }
+                        code
+                    }
+                    None => from,
+                };
+
+                let start = Instant::now();
+                let results = batch
+                    .read()
+                    .unwrap()
+                    .translate_multi(&session, &from, &text, &targets);
+                println!(
+                    "multi translation into {} targets took {:?}",
+                    targets.len(),
+                    start.elapsed()
+                );
+
+                let mut outputs = targets.into_iter().zip(results).map(|(target, result)| {
+                    let (text, route) = match result {
+                        Ok(output) => (output.text, output.route),
+                        Err(err) => (err.localized(), TranslationRoute::default()),
+                    };
+                    (target.to, text, route)
+                });
+                if let Some((_, text, route)) = outputs.next() {
+                    (ui.set_output_text)(text);
+                    (ui.set_translation_route)(route);
+                }
+                (ui.set_extra_outputs)(outputs.map(|(code, text, _)| (code, text)).collect());
             }
             IoEvent::RefreshTtsVoices {
                 language_code,
@@ -89,7 +307,7 @@ pub fn run_eventloop(bus_rx: Receiver<IoEvent>, ui: UiCallbacks, session: Arc<Tr
                     Ok(result) => {
                         let mut items = vec![TtsVoiceListItem {
                             name: String::new().into(),
-                            display_name: "Default".to_string().into(),
+                            display_name: tr("Default").into(),
                         }];
                         items.extend(result.voices.into_iter().map(|voice| TtsVoiceListItem {
                             name: voice.name.into(),
@@ -104,12 +322,7 @@ pub fn run_eventloop(bus_rx: Receiver<IoEvent>, ui: UiCallbacks, session: Arc<Tr
                     }
                     Err(err) => {
                         eprintln!("Failed to load TTS voices: {err}");
-                        (ui.set_tts_voices)(
-                            false,
-                            Vec::new(),
-                            String::new(),
-                            "Default".to_string(),
-                        );
+                        (ui.set_tts_voices)(false, Vec::new(), String::new(), tr("Default"));
                     }
                 }
             }
@@ -139,66 +352,148 @@ pub fn run_eventloop(bus_rx: Receiver<IoEvent>, ui: UiCallbacks, session: Arc<Tr
             }
             IoEvent::ImageTranslationRequest {
                 image_path,
+                generation,
+                page,
+                show,
+                reuse_page,
                 from,
                 to,
                 min_confidence,
                 max_image_size,
                 background_mode,
+                reading_order,
+                ocr_only,
+                regions,
+                corrections,
+                options,
             } => {
                 let start = Instant::now();
-                let result = image_ocr::translate_image_with_session(
-                    &session,
-                    std::path::Path::new(&image_path),
-                    &from,
-                    &to,
+                latest_generation.fetch_max(generation, Ordering::Relaxed);
+                let page_read = PageRead {
+                    image_path: image_path.clone(),
+                    from: from.clone(),
+                    to: to.clone(),
                     min_confidence,
                     max_image_size,
-                    &background_mode,
-                );
+                    background_mode,
+                    reading_order,
+                    ocr_only,
+                };
+                let read_page = {
+                    let session = session.clone();
+                    move || {
+                        image_ocr::translate_image_with_session(
+                            &session,
+                            std::path::Path::new(&image_path),
+                            &from,
+                            &to,
+                            min_confidence,
+                            max_image_size,
+                            background_mode,
+                            reading_order,
+                            ocr_only,
+                            &regions,
+                            &corrections,
+                            &options,
+                        )
+                    }
+                };
+
+                if !show {
+                    let ui = ui.clone();
+                    let page_cache = page_cache.clone();
+                    let latest_generation = latest_generation.clone();
+                    let _ = background_pages.send(Box::new(move || {
+                        // A newer document or settings replaced this one while it waited.
+                        if latest_generation.load(Ordering::Relaxed) != generation {
+                            return;
+                        }
+                        match read_page() {
+                            Ok(image_translation) => {
+                                page_cache.lock().unwrap().insert(
+                                    generation,
+                                    page,
+                                    page_read,
+                                    &image_translation,
+                                );
+                                (ui.set_image_page_text)(
+                                    generation,
+                                    page,
+                                    image_translation.extracted_text,
+                                    image_translation.translated_text,
+                                );
+                            }
+                            Err(message) => {
+                                (ui.set_image_page_text)(generation, page, String::new(), message);
+                            }
+                        }
+                    }));
+                    continue;
+                }
+
+                let cached = if reuse_page {
+                    page_cache.lock().unwrap().get(generation, page, &page_read)
+                } else {
+                    None
+                };
+                let result = match cached {
+                    Some(image_translation) => Ok(image_translation),
+                    None => {
+                        let result = read_page();
+                        if reuse_page && let Ok(image_translation) = &result {
+                            page_cache.lock().unwrap().insert(
+                                generation,
+                                page,
+                                page_read,
+                                image_translation,
+                            );
+                        }
+                        result
+                    }
+                };
 
                 match result {
                     Ok(image_translation) => {
+                        (ui.set_image_page_text)(
+                            generation,
+                            page,
+                            image_translation.extracted_text.clone(),
+                            image_translation.translated_text.clone(),
+                        );
                         let ui_start = Instant::now();
                         (ui.set_processed_image)(qimage_from_rgba_bytes(
                             image_translation.image_width,
                             image_translation.image_height,
                             &image_translation.cleaned_rgba_bytes,
                         ));
-                        send_detection_to_ui(&image_translation.extracted_text, &ui);
+                        send_detection_to_ui(
+                            &image_translation.extracted_text,
+                            &installed_codes,
+                            &ui,
+                        );
                         (ui.set_input_text)(image_translation.extracted_text);
                         (ui.set_output_text)(image_translation.translated_text);
+                        (ui.set_translation_route)(TranslationRoute::default());
                         let overlay_items = image_translation
                             .overlay_blocks
                             .into_iter()
                             .map(|block| ImageOverlayListItem {
-                                line_rects: serde_json::to_string(
-                                    &block
-                                        .lines
-                                        .iter()
-                                        .map(|line| {
-                                            serde_json::json!({
-                                                "x": line.x,
-                                                "y": line.y,
-                                                "width": line.width,
-                                                "height": line.height,
-                                                "foreground_color": argb_to_qml_color(line.foreground_argb).to_string(),
-                                            })
-                                        })
-                                        .collect::<Vec<_>>(),
-                                )
-                                .unwrap_or_else(|err| {
-                                    eprintln!("Failed to encode OCR line rects: {err}");
-                                    "[]".to_string()
-                                })
-                                .into(),
+                                line_rects: serde_json::to_string(&overlay_line_rects(&block))
+                                    .unwrap_or_else(|err| {
+                                        eprintln!("Failed to encode OCR line rects: {err}");
+                                        "[]".to_string()
+                                    })
+                                    .into(),
                                 block_x: block.x as f32,
                                 block_y: block.y as f32,
                                 block_width: block.width as f32,
                                 block_height: block.height as f32,
                                 suggested_font_size_px: block.suggested_font_size_px,
+                                source_text: block.source_text.into(),
                                 translated_text: block.translated_text.into(),
                                 background_color: argb_to_qml_color(block.background_argb),
                                 foreground_color: argb_to_qml_color(block.foreground_argb),
+                                right_to_left: block.reading_order == OcrReadingOrder::RightToLeft,
                             })
                             .collect::<Vec<_>>();
                         (ui.set_image_overlay)(
@@ -219,8 +514,86 @@ pub fn run_eventloop(bus_rx: Receiver<IoEvent>, ui: UiCallbacks, session: Arc<Tr
                 }
                 println!("image translation took {:?}", start.elapsed());
             }
+            IoEvent::DocumentPagesRequest { document_path } => {
+                let Some(app_paths) = app_paths.clone() else {
+                    continue;
+                };
+                let document = std::path::Path::new(&document_path);
+                let output_dir = std::path::Path::new(&app_paths.data).join(DOCUMENT_PAGES_DIR);
+                let pages = if image_ocr::is_pdf(document) {
+                    image_ocr::render_pdf_pages(document, &output_dir)
+                } else {
+                    image_ocr::split_tiff_pages(document, &output_dir)
+                };
+                match pages {
+                    Ok(pages) => (ui.set_image_pages)(
+                        pages
+                            .into_iter()
+                            .map(|page| format!("file://{}", page.display()))
+                            .collect(),
+                    ),
+                    Err(message) => {
+                        eprintln!("loading document pages failed: {message}");
+                        (ui.set_output_text)(message);
+                    }
+                }
+            }
+            IoEvent::ImageBlockTranslationRequest {
+                index,
+                text,
+                from,
+                to,
+                options,
+            } => {
+                let translated = translation::translate_text(&session, &from, &to, &text, &options)
+                    .map(|output| output.text)
+                    .map_err(|err| {
+                        eprintln!("image block translation failed: {err}");
+                        err.localized()
+                    });
+                (ui.set_image_block_translation)(index, text, translated);
+            }
+            IoEvent::ServiceTranslate {
+                text,
+                from,
+                to,
+                reply,
+            } => {
+                let from = if from == "auto" {
+                    detection::detect(&text, &installed_codes)
+                        .installed_source(&installed_codes)
+                        .map(|candidate| candidate.code.clone())
+                        .unwrap_or_default()
+                } else {
+                    from
+                };
+                let result = if from.is_empty() {
+                    Err("Could not detect the source language".to_string())
+                } else {
+                    let options = translation_settings.read().unwrap().options(&from, &to);
+                    batch
+                        .read()
+                        .unwrap()
+                        .translate_document(&session, &from, &to, &text, &options)
+                        .map(|output| output.text)
+                        .map_err(|err| err.to_string())
+                };
+                let _ = reply.send(result);
+            }
+            IoEvent::ServiceDetectLanguage { text, reply } => {
+                let _ = reply.send(detect_language_code(&text, &installed_codes));
+            }
+            IoEvent::ServiceListLanguages { reply } => {
+                let languages = languages_from_overview(session.language_overview())
+                    .into_iter()
+                    .filter(|language| language.core_installed || language.built_in)
+                    .map(|language| (language.code, language.name))
+                    .collect();
+                let _ = reply.send(languages);
+            }
             IoEvent::Shutdown => {
                 tts::stop_playback();
+                drop(api_server.take());
                 println!("shutdown signal, exiting");
                 break;
             }
@@ -271,12 +644,72 @@ fn download_feature(
     result
 }
 
-fn send_detection_to_ui(text: &str, ui: &UiCallbacks) {
-    let (detected, reliable) = detect_language(text, Format::Text);
+fn publish_languages(session: &TranslatorSession, ui: &UiCallbacks) -> Vec<String> {
+    let languages = languages_from_overview(session.language_overview());
+    let installed_codes = installed_source_codes(&languages);
+    (ui.set_languages)(languages);
+    installed_codes
+}
+
+fn send_detection_to_ui(
+    text: &str,
+    installed_codes: &[String],
+    ui: &UiCallbacks,
+) -> LanguageDetection {
+    let detection = detection::detect(text, installed_codes);
+    (ui.set_language_detection)(detection.clone());
+    detection
+}
+
+/// The detected language to translate from instead of the selected `from`, if
+/// any. Text already in one of the targets keeps the selected source.
+fn assumed_source(
+    detection: &LanguageDetection,
+    auto_source: bool,
+    from: &str,
+    targets: &[String],
+    installed_codes: &[String],
+) -> Option<String> {
+    let code = &detection
+        .installed_source(installed_codes)
+        .filter(|_| auto_source)?
+        .code;
+    (code != from && !targets.contains(code)).then(|| code.clone())
+}
+
+fn detect_language_code(text: &str, installed_codes: &[String]) -> String {
+    detection::detect(text, installed_codes)
+        .best_code()
+        .unwrap_or_default()
+        .to_string()
+}
 
-    let code = match (detected, reliable) {
-        (Some(c), cld2::Reliable) => c.0,
-        _ => "",
+/// Rects the overlay lays translated text into. Vertical blocks get horizontal
+/// rows instead of their narrow columns.
+fn overlay_line_rects(block: &image_ocr::ImageOverlayBlock) -> Vec<serde_json::Value> {
+    let rect_json = |rect: Rect, foreground_argb: u32| {
+        serde_json::json!({
+            "x": rect.x,
+            "y": rect.y,
+            "width": rect.width,
+            "height": rect.height,
+            "foreground_color": argb_to_qml_color(foreground_argb).to_string(),
+        })
     };
-    (ui.set_detected_language_code)(code.to_string());
+    if block.reading_order == OcrReadingOrder::Vertical {
+        let columns = block
+            .lines
+            .iter()
+            .map(|line| line.rect())
+            .collect::<Vec<_>>();
+        return horizontal_rows(block.rect(), &columns)
+            .into_iter()
+            .map(|row| rect_json(row, block.foreground_argb))
+            .collect();
+    }
+    block
+        .lines
+        .iter()
+        .map(|line| rect_json(line.rect(), line.foreground_argb))
+        .collect()
 }
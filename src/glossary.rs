@@ -0,0 +1,486 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+
+/// A required translation for a source term. A missing target means the term
+/// is kept as written.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GlossaryEntry {
+    pub source: String,
+    #[serde(default)]
+    pub target: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+#[serde(from = "StoredGlossary", into = "StoredGlossary")]
+pub struct Glossary {
+    /// Entries keyed by `(from, to)` language codes.
+    pub pairs: BTreeMap<(String, String), Vec<GlossaryEntry>>,
+}
+
+/// On-disk form. JSON object keys can't hold both codes unambiguously, so
+/// pairs are stored as a list.
+#[derive(Serialize, Deserialize)]
+struct StoredGlossary {
+    #[serde(default)]
+    pairs: Vec<StoredPair>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct StoredPair {
+    from: String,
+    to: String,
+    entries: Vec<GlossaryEntry>,
+}
+
+impl From<StoredGlossary> for Glossary {
+    fn from(stored: StoredGlossary) -> Self {
+        Self {
+            pairs: stored
+                .pairs
+                .into_iter()
+                .map(|pair| ((pair.from, pair.to), pair.entries))
+                .collect(),
+        }
+    }
+}
+
+impl From<Glossary> for StoredGlossary {
+    fn from(glossary: Glossary) -> Self {
+        Self {
+            pairs: glossary
+                .pairs
+                .into_iter()
+                .map(|((from, to), entries)| StoredPair { from, to, entries })
+                .collect(),
+        }
+    }
+}
+
+impl Glossary {
+    pub fn entries(&self, from: &str, to: &str) -> &[GlossaryEntry] {
+        self.pairs
+            .get(&pair_key(from, to))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Adds or replaces entries for a pair, matching source terms case-insensitively.
+    pub fn upsert(&mut self, from: &str, to: &str, entries: Vec<GlossaryEntry>) -> usize {
+        let pair = self.pairs.entry(pair_key(from, to)).or_default();
+        let mut count = 0;
+        for entry in entries {
+            let source = entry.source.trim();
+            if source.is_empty() {
+                continue;
+            }
+            let entry = GlossaryEntry {
+                source: source.to_string(),
+                target: entry
+                    .target
+                    .map(|target| target.trim().to_string())
+                    .filter(|target| !target.is_empty()),
+            };
+            match pair
+                .iter_mut()
+                .find(|existing| existing.source.to_lowercase() == entry.source.to_lowercase())
+            {
+                Some(existing) => *existing = entry,
+                None => pair.push(entry),
+            }
+            count += 1;
+        }
+        pair.sort_by_key(|entry| entry.source.to_lowercase());
+        count
+    }
+
+    pub fn remove(&mut self, from: &str, to: &str, index: usize) -> bool {
+        let key = pair_key(from, to);
+        let Some(pair) = self.pairs.get_mut(&key) else {
+            return false;
+        };
+        if index >= pair.len() {
+            return false;
+        }
+        pair.remove(index);
+        if pair.is_empty() {
+            self.pairs.remove(&key);
+        }
+        true
+    }
+}
+
+fn pair_key(from: &str, to: &str) -> (String, String) {
+    (from.to_string(), to.to_string())
+}
+
+pub fn load_glossary(config_dir: &str) -> Glossary {
+    let path = Path::new(config_dir).join("glossary.json");
+    let contents = match fs::read_to_string(&path) {
+        Ok(c) => c,
+        Err(_) => return Glossary::default(),
+    };
+    let glossary: Glossary = serde_json::from_str(&contents).unwrap_or_else(|err| {
+        eprintln!("glossary: failed to parse {}: {err}", path.display());
+        Glossary::default()
+    });
+    eprintln!(
+        "glossary: loaded {} pair(s) from {}",
+        glossary.pairs.len(),
+        path.display()
+    );
+    glossary
+}
+
+/// Writes through a temporary file, so a crash mid-save leaves the old
+/// glossary in place.
+pub fn save_glossary(config_dir: &str, glossary: &Glossary) -> Result<(), String> {
+    let path = Path::new(config_dir).join("glossary.json");
+    let tmp_path = path.with_extension("json.tmp");
+    let json = serde_json::to_string_pretty(glossary).expect("glossary should serialize");
+    fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(json.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, &path))
+        .map_err(|err| {
+            let _ = fs::remove_file(&tmp_path);
+            format!("Failed to save glossary {}: {err}", path.display())
+        })
+}
+
+/// Reads a CSV or TBX file into entries for the `from`->`to` pair.
+pub fn import_glossary_file(
+    path: &Path,
+    from: &str,
+    to: &str,
+) -> Result<Vec<GlossaryEntry>, String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    let extension = path
+        .extension()
+        .and_then(|extension| extension.to_str())
+        .unwrap_or_default()
+        .to_lowercase();
+
+    let entries = match extension.as_str() {
+        "tbx" | "xml" => parse_tbx(&contents, from, to),
+        _ => parse_csv(&contents, from, to),
+    };
+    if entries.is_empty() {
+        return Err(format!("No {from}->{to} terms found in {}", path.display()));
+    }
+    Ok(entries)
+}
+
+/// Accepts `source,target` rows separated by commas, semicolons or tabs. An
+/// empty target marks the term as "do not translate".
+fn parse_csv(contents: &str, from: &str, to: &str) -> Vec<GlossaryEntry> {
+    let delimiter = [',', ';', '\t']
+        .into_iter()
+        .max_by_key(|delimiter| {
+            // Counted outside quotes, which may hold any of them.
+            let mut in_quotes = false;
+            contents
+                .chars()
+                .filter(|ch| {
+                    if *ch == '"' {
+                        in_quotes = !in_quotes;
+                    }
+                    !in_quotes && ch == delimiter
+                })
+                .count()
+        })
+        .unwrap_or(',');
+
+    let mut rows = csv_records(contents, delimiter)
+        .into_iter()
+        .filter(|fields| fields.iter().any(|field| !field.trim().is_empty()))
+        .peekable();
+
+    if let Some(header) = rows.peek() {
+        let names = header
+            .iter()
+            .map(|field| field.trim().to_lowercase())
+            .collect::<Vec<_>>();
+        let is_header = matches!(
+            names.first().map(String::as_str),
+            Some("source" | "term" | "src")
+        ) || (names.first() == Some(&from.to_lowercase())
+            && names.get(1) == Some(&to.to_lowercase()));
+        if is_header {
+            rows.next();
+        }
+    }
+
+    rows.map(|fields| GlossaryEntry {
+        source: fields.first().cloned().unwrap_or_default(),
+        target: fields.get(1).cloned(),
+    })
+    .filter(|entry| !entry.source.trim().is_empty())
+    .collect()
+}
+
+/// Splits CSV/TSV text into records. Quoted fields may contain the delimiter,
+/// doubled quotes and line breaks; records end at an unquoted `\n` or `\r\n`.
+fn csv_records(contents: &str, delimiter: char) -> Vec<Vec<String>> {
+    let mut records = Vec::new();
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = contents.chars().peekable();
+
+    while let Some(ch) = chars.next() {
+        match ch {
+            '"' if in_quotes && chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+            }
+            '"' => in_quotes = !in_quotes,
+            ch if ch == delimiter && !in_quotes => fields.push(std::mem::take(&mut current)),
+            '\r' if !in_quotes && chars.peek() == Some(&'\n') => {}
+            '\n' if !in_quotes => {
+                fields.push(std::mem::take(&mut current));
+                records.push(std::mem::take(&mut fields));
+            }
+            ch => current.push(ch),
+        }
+    }
+    if !current.is_empty() || !fields.is_empty() {
+        fields.push(current);
+        records.push(fields);
+    }
+    records
+}
+
+/// Minimal TBX reader: takes the first term of each `langSet`/`langSec` whose
+/// `xml:lang` matches the pair. A tag matching a code in full, such as
+/// `zh-Hant` or `pt-BR`, wins over one sharing only its primary subtag.
+fn parse_tbx(contents: &str, from: &str, to: &str) -> Vec<GlossaryEntry> {
+    let mut entries = Vec::new();
+
+    for concept in xml_elements(contents, &["termEntry", "conceptEntry"]) {
+        let mut source = (0, None);
+        let mut target = (0, None);
+        for (attributes, body) in xml_elements_with_attributes(concept, &["langSet", "langSec"]) {
+            let Some(lang) = xml_attribute(attributes, "xml:lang") else {
+                continue;
+            };
+            let Some(term) = xml_elements(body, &["term"]).into_iter().next() else {
+                continue;
+            };
+            let source_match = language_match(lang, from);
+            let target_match = language_match(lang, to);
+            if source_match > source.0 && source_match >= target_match {
+                source = (source_match, Some(xml_unescape(term.trim())));
+            } else if target_match > target.0 && target_match > source_match {
+                target = (target_match, Some(xml_unescape(term.trim())));
+            }
+        }
+
+        if let (_, Some(source)) = source {
+            entries.push(GlossaryEntry {
+                source,
+                target: target.1,
+            });
+        }
+    }
+
+    entries
+}
+
+/// 2 when the tags are equal, 1 when only their primary subtags are, else 0;
+/// case and `_` versus `-` are ignored.
+fn language_match(tag: &str, code: &str) -> u8 {
+    let normalize = |tag: &str| tag.trim().replace('_', "-").to_lowercase();
+    let (tag, code) = (normalize(tag), normalize(code));
+    let primary = |tag: &str| tag.split('-').next().unwrap_or_default().to_string();
+    if tag == code {
+        2
+    } else if primary(&tag) == primary(&code) {
+        1
+    } else {
+        0
+    }
+}
+
+fn xml_elements<'a>(contents: &'a str, names: &[&str]) -> Vec<&'a str> {
+    xml_elements_with_attributes(contents, names)
+        .into_iter()
+        .map(|(_, body)| body)
+        .collect()
+}
+
+fn xml_elements_with_attributes<'a>(contents: &'a str, names: &[&str]) -> Vec<(&'a str, &'a str)> {
+    let mut elements = Vec::new();
+    let mut rest = contents;
+
+    while let Some((name, open_start)) = names
+        .iter()
+        .filter_map(|name| find_open_tag(rest, name).map(|start| (*name, start)))
+        .min_by_key(|(_, start)| *start)
+    {
+        let after_name = &rest[open_start + 1 + name.len()..];
+        let Some(tag_end) = after_name.find('>') else {
+            break;
+        };
+        let attributes = &after_name[..tag_end];
+        let body_start = &after_name[tag_end + 1..];
+        if attributes.ends_with('/') {
+            rest = body_start;
+            continue;
+        }
+        let close_tag = format!("</{name}>");
+        let Some(close) = body_start.find(&close_tag) else {
+            break;
+        };
+        elements.push((attributes, &body_start[..close]));
+        rest = &body_start[close + close_tag.len()..];
+    }
+
+    elements
+}
+
+fn find_open_tag(contents: &str, name: &str) -> Option<usize> {
+    let pattern = format!("<{name}");
+    let mut offset = 0;
+    while let Some(found) = contents[offset..].find(&pattern) {
+        let start = offset + found;
+        let next = contents[start + pattern.len()..].chars().next();
+        if matches!(next, Some('>' | '/' | ' ' | '\t' | '\r' | '\n')) {
+            return Some(start);
+        }
+        offset = start + pattern.len();
+    }
+    None
+}
+
+fn xml_attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    let start = attributes.find(&format!("{name}="))? + name.len() + 1;
+    let quote = attributes[start..].chars().next()?;
+    let value = &attributes[start + 1..];
+    value.find(quote).map(|end| &value[..end])
+}
+
+fn xml_unescape(value: &str) -> String {
+    value
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_csv_with_header_and_quotes() {
+        let csv = "source;target\n\"Acme, Inc.\";\nFreigabe;approval\n";
+        let entries = parse_csv(csv, "de", "en");
+        assert_eq!(
+            entries,
+            vec![
+                GlossaryEntry {
+                    source: "Acme, Inc.".to_string(),
+                    target: Some(String::new()),
+                },
+                GlossaryEntry {
+                    source: "Freigabe".to_string(),
+                    target: Some("approval".to_string()),
+                },
+            ]
+        );
+
+        let mut glossary = Glossary::default();
+        assert_eq!(glossary.upsert("de", "en", entries), 2);
+        assert_eq!(glossary.entries("de", "en")[0].target, None);
+
+        let multiline = "\"Zeile eins\r\nZeile zwei\",\"line one\nline two\"\r\nAbc,xyz\r\n";
+        assert_eq!(
+            parse_csv(multiline, "de", "en"),
+            vec![
+                GlossaryEntry {
+                    source: "Zeile eins\r\nZeile zwei".to_string(),
+                    target: Some("line one\nline two".to_string()),
+                },
+                GlossaryEntry {
+                    source: "Abc".to_string(),
+                    target: Some("xyz".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn keys_pairs_by_both_codes() {
+        let mut glossary = Glossary::default();
+        glossary.upsert("zh-Hant", "en", vec![entry("甲")]);
+        glossary.upsert("zh", "Hant-en", vec![entry("乙")]);
+        assert_eq!(glossary.entries("zh-Hant", "en")[0].source, "甲");
+
+        let json = serde_json::to_string(&glossary).unwrap();
+        let loaded: Glossary = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.entries("zh", "Hant-en")[0].source, "乙");
+    }
+
+    fn entry(source: &str) -> GlossaryEntry {
+        GlossaryEntry {
+            source: source.to_string(),
+            target: None,
+        }
+    }
+
+    #[test]
+    fn parses_tbx_lang_sets() {
+        let tbx = r#"<martif><text><body>
+            <termEntry id="1">
+              <langSet xml:lang="de-DE"><tig><term>Vertrag &amp; Anlage</term></tig></langSet>
+              <langSet xml:lang="en"><tig><term>contract &amp; annex</term></tig></langSet>
+            </termEntry>
+            <termEntry id="2">
+              <langSet xml:lang="de"><tig><term>Gerätename</term></tig></langSet>
+            </termEntry>
+        </body></text></martif>"#;
+        assert_eq!(
+            parse_tbx(tbx, "de", "en"),
+            vec![
+                GlossaryEntry {
+                    source: "Vertrag & Anlage".to_string(),
+                    target: Some("contract & annex".to_string()),
+                },
+                GlossaryEntry {
+                    source: "Gerätename".to_string(),
+                    target: None,
+                },
+            ]
+        );
+
+        let scripts = r#"<tbx><text><body>
+            <conceptEntry id="1">
+              <langSec xml:lang="zh_Hans"><termSec><term>软件</term></termSec></langSec>
+              <langSec xml:lang="zh-Hant"><termSec><term>軟體</term></termSec></langSec>
+              <langSec xml:lang="pt-PT"><termSec><term>ficheiro</term></termSec></langSec>
+              <langSec xml:lang="pt-BR"><termSec><term>arquivo</term></termSec></langSec>
+            </conceptEntry>
+        </body></text></tbx>"#;
+        assert_eq!(
+            parse_tbx(scripts, "zh-Hant", "pt-BR"),
+            vec![GlossaryEntry {
+                source: "軟體".to_string(),
+                target: Some("arquivo".to_string()),
+            }]
+        );
+        assert_eq!(
+            parse_tbx(scripts, "ZH", "pt")[0],
+            GlossaryEntry {
+                source: "软件".to_string(),
+                target: Some("ficheiro".to_string()),
+            }
+        );
+    }
+}
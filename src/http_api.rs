@@ -0,0 +1,513 @@
+use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use serde_json::{Value, json};
+use translator::TranslatorSession;
+
+use crate::batch::BatchTranslator;
+use crate::catalog_state::{installed_source_codes, languages_from_overview};
+use crate::detection;
+use crate::model::Direction;
+use crate::translation::TranslationSettings;
+
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+const MAX_LINE_BYTES: usize = 8 * 1024;
+const MAX_HEADERS: usize = 64;
+const MAX_CONNECTIONS: usize = 8;
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(200);
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Loopback-only HTTP server speaking the LibreTranslate `/translate`,
+/// `/detect` and `/languages` endpoints. No CORS headers are sent and the
+/// Host header must name the loopback address, so web pages can't reach it.
+pub struct ApiServer {
+    port: u16,
+    shutdown: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl ApiServer {
+    pub fn start(
+        port: u16,
+        session: Arc<TranslatorSession>,
+        batch: Arc<RwLock<BatchTranslator>>,
+        settings: Arc<RwLock<TranslationSettings>>,
+    ) -> Result<Self, String> {
+        let address = SocketAddr::from((Ipv4Addr::LOCALHOST, port));
+        let listener = TcpListener::bind(address)
+            .map_err(|err| format!("Can't listen on {address}: {err}"))?;
+        listener
+            .set_nonblocking(true)
+            .map_err(|err| format!("Can't configure {address}: {err}"))?;
+
+        let shutdown = Arc::new(AtomicBool::new(false));
+        let accept_shutdown = shutdown.clone();
+        let context = Arc::new(ServerContext {
+            port,
+            session,
+            batch,
+            settings,
+        });
+        let handle = thread::Builder::new()
+            .name("http-api".to_string())
+            .spawn(move || accept_loop(listener, context, accept_shutdown))
+            .map_err(|err| format!("Can't start API server thread: {err}"))?;
+
+        println!("http api: listening on http://{address}");
+        Ok(Self {
+            port,
+            shutdown,
+            handle: Some(handle),
+        })
+    }
+
+    pub fn port(&self) -> u16 {
+        self.port
+    }
+}
+
+impl Drop for ApiServer {
+    fn drop(&mut self) {
+        self.shutdown.store(true, Ordering::Relaxed);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+        println!("http api: stopped on port {}", self.port);
+    }
+}
+
+struct ServerContext {
+    port: u16,
+    session: Arc<TranslatorSession>,
+    /// The event loop's workers, so API requests split documents the same way.
+    batch: Arc<RwLock<BatchTranslator>>,
+    settings: Arc<RwLock<TranslationSettings>>,
+}
+
+/// Frees a connection slot when its handler thread finishes.
+struct ConnectionSlot(Arc<AtomicUsize>);
+
+impl Drop for ConnectionSlot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+fn accept_loop(listener: TcpListener, context: Arc<ServerContext>, shutdown: Arc<AtomicBool>) {
+    let active = Arc::new(AtomicUsize::new(0));
+    while !shutdown.load(Ordering::Relaxed) {
+        match listener.accept() {
+            Ok((mut stream, _)) => {
+                if active.fetch_add(1, Ordering::Relaxed) >= MAX_CONNECTIONS {
+                    active.fetch_sub(1, Ordering::Relaxed);
+                    let _ = write_response(
+                        &mut stream,
+                        503,
+                        &json!({ "error": "Too many requests in progress" }),
+                    );
+                    continue;
+                }
+                let slot = ConnectionSlot(active.clone());
+                let context = context.clone();
+                let spawned = thread::Builder::new()
+                    .name("http-api-connection".to_string())
+                    .spawn(move || {
+                        let _slot = slot;
+                        if let Err(err) = handle_connection(stream, &context) {
+                            eprintln!("http api: {err}");
+                        }
+                    });
+                if let Err(err) = spawned {
+                    eprintln!("http api: can't start connection thread: {err}");
+                }
+            }
+            Err(err) if err.kind() == std::io::ErrorKind::WouldBlock => {
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+            Err(err) => {
+                eprintln!("http api: accept failed: {err}");
+                thread::sleep(ACCEPT_POLL_INTERVAL);
+            }
+        }
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    host: String,
+    content_type: String,
+    body: Vec<u8>,
+}
+
+fn handle_connection(stream: TcpStream, context: &ServerContext) -> Result<(), String> {
+    stream
+        .set_nonblocking(false)
+        .and_then(|_| stream.set_read_timeout(Some(READ_TIMEOUT)))
+        .map_err(|err| format!("socket setup failed: {err}"))?;
+    let mut reader = BufReader::new(stream.try_clone().map_err(|err| err.to_string())?);
+    let mut writer = stream;
+
+    let (status, body) = match read_request(&mut reader) {
+        Ok(request) if !is_loopback_host(&request.host, context.port) => {
+            (403, json!({ "error": "Forbidden host" }))
+        }
+        Ok(request) => route(&request, context),
+        Err(message) => (400, json!({ "error": message })),
+    };
+    write_response(&mut writer, status, &body).map_err(|err| format!("write failed: {err}"))
+}
+
+/// Only `127.0.0.1:<port>` and `localhost:<port>` are served, which stops
+/// DNS rebinding from reaching the API through a browser.
+fn is_loopback_host(host: &str, port: u16) -> bool {
+    let host = host.to_ascii_lowercase();
+    host == format!("127.0.0.1:{port}") || host == format!("localhost:{port}")
+}
+
+/// Reads one CRLF- or LF-terminated line of at most `MAX_LINE_BYTES`.
+fn read_line_limited(reader: &mut impl BufRead, what: &str) -> Result<String, String> {
+    let mut line = String::new();
+    reader
+        .take(MAX_LINE_BYTES as u64 + 1)
+        .read_line(&mut line)
+        .map_err(|err| format!("Failed to read {what}: {err}"))?;
+    if line.len() > MAX_LINE_BYTES {
+        return Err(format!("The {what} is too long"));
+    }
+    Ok(line)
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request, String> {
+    let request_line = read_line_limited(reader, "request line")?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let target = parts.next().unwrap_or_default();
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+
+    let mut content_length = 0;
+    let mut content_type = String::new();
+    let mut host = String::new();
+    for header_count in 0.. {
+        let line = read_line_limited(reader, "header")?;
+        let line = line.trim_end();
+        if line.is_empty() {
+            break;
+        }
+        if header_count == MAX_HEADERS {
+            return Err("Too many headers".to_string());
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            let value = value.trim();
+            match name.trim().to_ascii_lowercase().as_str() {
+                "content-length" => {
+                    content_length = value
+                        .parse::<usize>()
+                        .map_err(|_| "Invalid Content-Length".to_string())?
+                }
+                "content-type" => content_type = value.to_ascii_lowercase(),
+                "host" => host = value.to_string(),
+                _ => {}
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err("Request body too large".to_string());
+    }
+
+    let mut body = vec![0; content_length];
+    reader
+        .read_exact(&mut body)
+        .map_err(|err| format!("Failed to read body: {err}"))?;
+    if body.is_empty() && !query.is_empty() {
+        body = query.as_bytes().to_vec();
+        content_type = "application/x-www-form-urlencoded".to_string();
+    }
+
+    Ok(Request {
+        method,
+        path: path.trim_end_matches('/').to_string(),
+        host,
+        content_type,
+        body,
+    })
+}
+
+fn route(request: &Request, context: &ServerContext) -> (u16, Value) {
+    match (request.method.as_str(), request.path.as_str()) {
+        ("GET", "/languages") => (200, languages_response(&context.session)),
+        ("POST", "/translate") | ("GET", "/translate") => match parse_params(request) {
+            Ok(params) => translate_response(&params, context),
+            Err(message) => (400, json!({ "error": message })),
+        },
+        ("POST", "/detect") | ("GET", "/detect") => match parse_params(request) {
+            Ok(params) => detect_response(&params, &context.session),
+            Err(message) => (400, json!({ "error": message })),
+        },
+        _ => (404, json!({ "error": "Not found" })),
+    }
+}
+
+/// Accepts both JSON and form-encoded parameters, as LibreTranslate does. A
+/// form key given more than once collects its values into a list, so `q` can
+/// carry several texts either way.
+fn parse_params(request: &Request) -> Result<HashMap<String, Value>, String> {
+    let body = std::str::from_utf8(&request.body).map_err(|_| "Body is not UTF-8".to_string())?;
+    if request.content_type.starts_with("application/json") {
+        return match serde_json::from_str::<Value>(body) {
+            Ok(Value::Object(map)) => Ok(map.into_iter().collect()),
+            Ok(_) => Err("Expected a JSON object".to_string()),
+            Err(err) => Err(format!("Invalid JSON: {err}")),
+        };
+    }
+
+    let mut params = HashMap::new();
+    for pair in body.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let value = Value::String(url_decode(value));
+        match params.entry(url_decode(key)) {
+            Entry::Vacant(entry) => {
+                entry.insert(value);
+            }
+            Entry::Occupied(mut entry) => match entry.get_mut() {
+                Value::Array(values) => values.push(value),
+                first => *first = Value::Array(vec![first.take(), value]),
+            },
+        }
+    }
+    Ok(params)
+}
+
+/// Malformed escapes are kept as written; bytes that aren't UTF-8 become
+/// replacement characters.
+fn url_decode(value: &str) -> String {
+    let bytes = value.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+    while index < bytes.len() {
+        match bytes[index] {
+            b'+' => decoded.push(b' '),
+            b'%' => match value
+                .get(index + 1..index + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                Some(byte) => {
+                    decoded.push(byte);
+                    index += 2;
+                }
+                None => decoded.push(b'%'),
+            },
+            byte => decoded.push(byte),
+        }
+        index += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn param_str<'a>(params: &'a HashMap<String, Value>, name: &str) -> Option<&'a str> {
+    params
+        .get(name)
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+fn translate_response(params: &HashMap<String, Value>, context: &ServerContext) -> (u16, Value) {
+    let Some(query) = params.get("q") else {
+        return (
+            400,
+            json!({ "error": "Invalid request: missing q parameter" }),
+        );
+    };
+    let Some(target) = param_str(params, "target") else {
+        return (
+            400,
+            json!({ "error": "Invalid request: missing target parameter" }),
+        );
+    };
+    let source = param_str(params, "source").unwrap_or("auto");
+
+    let texts = match query {
+        Value::String(text) => Some(vec![text.as_str()]),
+        Value::Array(items) => items.iter().map(Value::as_str).collect(),
+        _ => None,
+    };
+    let Some(texts) = texts else {
+        return (400, json!({ "error": "Invalid request: q must be text" }));
+    };
+
+    let detection = (source == "auto").then(|| {
+        let installed_codes = installed_codes(&context.session);
+        detection::detect(&texts.join("\n\n"), &installed_codes)
+            .installed_source(&installed_codes)
+            .cloned()
+    });
+    let source = match &detection {
+        Some(Some(candidate)) => candidate.code.as_str(),
+        Some(None) => {
+            return (
+                400,
+                json!({ "error": "Could not detect the source language" }),
+            );
+        }
+        None => source,
+    };
+
+    let options = context.settings.read().unwrap().options(source, target);
+    let batch = context.batch.read().unwrap();
+    let mut translated = Vec::with_capacity(texts.len());
+    for text in &texts {
+        match batch.translate_document(&context.session, source, target, text, &options) {
+            Ok(output) => translated.push(output.text),
+            Err(err) => return (400, json!({ "error": err.to_string() })),
+        }
+    }
+
+    let translated_text = match query {
+        Value::Array(_) => json!(translated),
+        _ => json!(translated.into_iter().next().unwrap_or_default()),
+    };
+    let mut response = json!({ "translatedText": translated_text });
+    if let Some(Some(candidate)) = detection {
+        response["detectedLanguage"] =
+            json!({ "language": candidate.code, "confidence": candidate.percent });
+    }
+    (200, response)
+}
+
+fn detect_response(params: &HashMap<String, Value>, session: &TranslatorSession) -> (u16, Value) {
+    let Some(text) = param_str(params, "q") else {
+        return (
+            400,
+            json!({ "error": "Invalid request: missing q parameter" }),
+        );
+    };
+    let candidates = detection::detect(text, &installed_codes(session)).candidates;
+    (
+        200,
+        Value::Array(
+            candidates
+                .iter()
+                .map(|candidate| json!({ "language": candidate.code, "confidence": candidate.percent }))
+                .collect(),
+        ),
+    )
+}
+
+/// Same prior the app's own detection uses.
+fn installed_codes(session: &TranslatorSession) -> Vec<String> {
+    installed_source_codes(&languages_from_overview(session.language_overview()))
+}
+
+fn languages_response(session: &TranslatorSession) -> Value {
+    let languages = languages_from_overview(session.language_overview())
+        .into_iter()
+        .filter(|language| language.core_installed || language.built_in)
+        .collect::<Vec<_>>();
+    let targets = languages
+        .iter()
+        .filter(|language| matches!(language.direction, Direction::ToOnly | Direction::Both))
+        .map(|language| language.code.as_str())
+        .collect::<Vec<_>>();
+
+    Value::Array(
+        languages
+            .iter()
+            .filter(|language| matches!(language.direction, Direction::FromOnly | Direction::Both))
+            .map(|language| {
+                json!({
+                    "code": language.code,
+                    "name": language.name,
+                    "targets": targets
+                        .iter()
+                        .filter(|code| **code != language.code)
+                        .collect::<Vec<_>>(),
+                })
+            })
+            .collect(),
+    )
+}
+
+fn write_response(writer: &mut impl Write, status: u16, body: &Value) -> std::io::Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    };
+    let body = body.to_string();
+    write!(
+        writer,
+        "HTTP/1.1 {status} {reason}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{body}",
+        body.len()
+    )?;
+    writer.flush()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_oversized_requests_and_foreign_hosts() {
+        let request =
+            "POST /translate/ HTTP/1.1\r\nHost: localhost:5000\r\nContent-Length: 2\r\n\r\n{}";
+        let request = read_request(&mut request.as_bytes()).unwrap();
+        assert_eq!(request.path, "/translate");
+        assert!(is_loopback_host(&request.host, 5000));
+        assert!(!is_loopback_host(&request.host, 5001));
+        assert!(!is_loopback_host("attacker.example:5000", 5000));
+
+        let long_line = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE_BYTES));
+        assert!(read_request(&mut long_line.as_bytes()).is_err());
+
+        let many_headers = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X-A: b\r\n".repeat(MAX_HEADERS + 1)
+        );
+        assert!(read_request(&mut many_headers.as_bytes()).is_err());
+    }
+
+    #[test]
+    fn decodes_form_parameters() {
+        assert_eq!(url_decode("caf%C3%A9+au%20lait"), "café au lait");
+        assert_eq!(url_decode("1%2B1%3D2"), "1+1=2");
+        assert_eq!(url_decode("100%"), "100%");
+        assert_eq!(url_decode("%zz%4"), "%zz%4");
+        assert_eq!(url_decode("%ff"), "\u{fffd}");
+
+        let form = |body: &str| Request {
+            method: "POST".to_string(),
+            path: "/translate".to_string(),
+            host: String::new(),
+            content_type: "application/x-www-form-urlencoded".to_string(),
+            body: body.as_bytes().to_vec(),
+        };
+        let params = parse_params(&form("q=Hallo+Welt&target=en&source=&flag")).unwrap();
+        assert_eq!(params["q"], json!("Hallo Welt"));
+        assert_eq!(params["target"], json!("en"));
+        assert_eq!(param_str(&params, "source"), None);
+        assert_eq!(params["flag"], json!(""));
+
+        let params = parse_params(&form("q=eins&target=en&q=zwei&q=drei%26vier")).unwrap();
+        assert_eq!(params["q"], json!(["eins", "zwei", "drei&vier"]));
+
+        let mut json_request = form(r#"{"q":["a","b"],"target":"de"}"#);
+        json_request.content_type = "application/json; charset=utf-8".to_string();
+        let params = parse_params(&json_request).unwrap();
+        assert_eq!(params["q"], json!(["a", "b"]));
+        json_request.body = b"[1]".to_vec();
+        assert!(parse_params(&json_request).is_err());
+    }
+}
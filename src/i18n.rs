@@ -0,0 +1,121 @@
+use std::collections::HashMap;
+use std::sync::OnceLock;
+
+/// Bundled message catalogs, keyed by locale. Message ids are the English
+/// strings themselves, with `{name}` placeholders.
+const CATALOGS: &[(&str, &str)] = &[("de", include_str!("../data/i18n/de.json"))];
+
+static CATALOG: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Loads the catalog for the environment's locale. Safe to call more than once;
+/// only the first call has an effect.
+pub fn init() {
+    let locale = env_locales()
+        .into_iter()
+        .find(|locale| CATALOGS.iter().any(|(code, _)| code == locale));
+    let catalog = locale
+        .and_then(|locale| {
+            let (_, source) = CATALOGS.iter().find(|(code, _)| *code == locale)?;
+            match serde_json::from_str::<HashMap<String, String>>(source) {
+                Ok(catalog) => {
+                    println!("i18n: using '{locale}' catalog");
+                    Some(catalog)
+                }
+                Err(err) => {
+                    eprintln!("i18n: invalid '{locale}' catalog: {err}");
+                    None
+                }
+            }
+        })
+        .unwrap_or_default();
+    let _ = CATALOG.set(catalog);
+}
+
+pub fn tr(message: &str) -> String {
+    CATALOG
+        .get()
+        .and_then(|catalog| catalog.get(message))
+        .filter(|translated| !translated.is_empty())
+        .cloned()
+        .unwrap_or_else(|| message.to_string())
+}
+
+/// Translates `message` and fills in its `{name}` placeholders.
+pub fn tr_args(message: &str, args: &[(&str, &str)]) -> String {
+    fill(&tr(message), args)
+}
+
+fn fill(message: &str, args: &[(&str, &str)]) -> String {
+    args.iter()
+        .fold(message.to_string(), |text, (name, value)| {
+            text.replace(&format!("{{{name}}}"), value)
+        })
+}
+
+/// Candidate catalog names from `LANGUAGE`, `LC_ALL`, `LC_MESSAGES` and `LANG`,
+/// most specific first: `de_AT.UTF-8` yields `de_AT` then `de`.
+fn env_locales() -> Vec<String> {
+    let language = std::env::var("LANGUAGE").unwrap_or_default();
+    let primary = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty())
+        .unwrap_or_default();
+    locale_candidates(language.split(':').chain(std::iter::once(primary.as_str())))
+}
+
+fn locale_candidates<'a>(values: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+    let mut candidates = Vec::new();
+    for value in values {
+        let locale = value.split(['.', '@']).next().unwrap_or_default();
+        if locale.is_empty() || locale == "C" || locale == "POSIX" {
+            continue;
+        }
+        let base = locale.split(['_', '-']).next().unwrap_or(locale);
+        for candidate in [locale, base] {
+            if !candidates.iter().any(|existing| existing == candidate) {
+                candidates.push(candidate.to_string());
+            }
+        }
+    }
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_locales_and_fills_placeholders() {
+        assert_eq!(
+            locale_candidates(["pt_BR", "fr", "de_AT.UTF-8@euro", "C"]),
+            ["pt_BR", "pt", "fr", "de_AT", "de"]
+        );
+        assert_eq!(
+            fill(
+                "No {language} dictionary installed",
+                &[("language", "German")]
+            ),
+            "No German dictionary installed"
+        );
+        for (locale, source) in CATALOGS {
+            let catalog = serde_json::from_str::<HashMap<String, String>>(source).unwrap();
+            for (message, translated) in catalog.iter().filter(|(_, text)| !text.is_empty()) {
+                let placeholders = |text: &str| {
+                    let mut names = text
+                        .split('{')
+                        .skip(1)
+                        .filter_map(|rest| rest.split_once('}').map(|(name, _)| name.to_string()))
+                        .collect::<Vec<_>>();
+                    names.sort();
+                    names
+                };
+                assert_eq!(
+                    placeholders(message),
+                    placeholders(translated),
+                    "{locale}: {message}"
+                );
+            }
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use qmetaobject::{QImage, QRectF, QString};
+use serde::Deserialize;
+
+use crate::rendered_image_item::{paint_text_line, text_size};
+use crate::ui::ImageOverlayListItem;
+
+/// Smallest text size, and how far above the engine's suggestion fitting
+/// starts, as in the overlay drawn by `TranslatedImageView.qml`.
+const MIN_PIXEL_SIZE: u32 = 8;
+const PIXEL_SIZE_HEADROOM: u32 = 4;
+
+#[derive(Debug, Deserialize)]
+struct LineRect {
+    x: f32,
+    y: f32,
+    width: f32,
+    height: f32,
+    #[serde(default)]
+    foreground_color: String,
+}
+
+/// Draws the translated blocks onto a copy of `image`, laid out over their
+/// line rects the same way the on-screen overlay is.
+pub fn burn_in_overlay(image: &QImage, items: &[ImageOverlayListItem]) -> QImage {
+    let mut result = image.clone();
+    for item in items {
+        let text = item.translated_text.to_string();
+        if text.trim().is_empty() {
+            continue;
+        }
+        let rects =
+            serde_json::from_str::<Vec<LineRect>>(&item.line_rects.to_string()).unwrap_or_default();
+        let (pixel_size, lines) = fit_block(&text, &rects, item.suggested_font_size_px);
+        for (rect, line) in rects.iter().zip(&lines) {
+            let foreground = if rect.foreground_color.is_empty() {
+                item.foreground_color.clone()
+            } else {
+                QString::from(rect.foreground_color.as_str())
+            };
+            paint_text_line(
+                &mut result,
+                QRectF {
+                    x: rect.x as f64,
+                    y: rect.y as f64,
+                    width: rect.width as f64,
+                    height: rect.height as f64,
+                },
+                line,
+                pixel_size,
+                &item.background_color,
+                &foreground,
+                item.right_to_left,
+            );
+        }
+    }
+    result
+}
+
+/// Largest pixel size whose lines fit, starting a little above the engine's
+/// suggestion; falls back to the smallest size with whatever fit.
+fn fit_block(text: &str, rects: &[LineRect], suggested_px: f32) -> (u32, Vec<String>) {
+    let start = (suggested_px.ceil().max(0.0) as u32 + PIXEL_SIZE_HEADROOM).max(MIN_PIXEL_SIZE);
+    let mut last = Vec::new();
+    for pixel_size in (MIN_PIXEL_SIZE..=start).rev() {
+        let (fits, lines) = fit_lines(text, rects, |line| {
+            let size = text_size(line, pixel_size);
+            (size.width as f32, size.height as f32)
+        });
+        if fits {
+            return (pixel_size, lines);
+        }
+        last = lines;
+    }
+    (MIN_PIXEL_SIZE, last)
+}
+
+/// Breaks `text` into one line per rect, at spaces where possible. `measure`
+/// gives a line's width and height; reports whether all the text fit.
+fn fit_lines(
+    text: &str,
+    rects: &[LineRect],
+    measure: impl Fn(&str) -> (f32, f32),
+) -> (bool, Vec<String>) {
+    let chars = text.chars().collect::<Vec<_>>();
+    let mut start = 0;
+    let mut lines = Vec::new();
+    for rect in rects {
+        start = skip_separators(&chars, start);
+        if start >= chars.len() {
+            break;
+        }
+        let newline = chars[start..]
+            .iter()
+            .position(|&ch| ch == '\n')
+            .map_or(chars.len(), |offset| start + offset);
+
+        let counted = count_fitting(&chars[start..newline], rect.width, &measure);
+        if counted == 0 {
+            return (false, lines);
+        }
+        let raw_end = start + counted;
+        let mut end = if raw_end >= newline {
+            newline
+        } else {
+            break_index(&chars, start, raw_end)
+        };
+        if end <= start {
+            end = raw_end;
+        }
+
+        let line = chars[start..end]
+            .iter()
+            .collect::<String>()
+            .trim_end()
+            .to_string();
+        let (_, height) = measure(if line.is_empty() { "Ag" } else { &line });
+        if height > rect.height {
+            return (false, lines);
+        }
+        lines.push(line);
+        start = end;
+    }
+    (skip_separators(&chars, start) >= chars.len(), lines)
+}
+
+fn skip_separators(chars: &[char], mut index: usize) -> usize {
+    while chars
+        .get(index)
+        .is_some_and(|ch| matches!(ch, ' ' | '\t' | '\r' | '\n'))
+    {
+        index += 1;
+    }
+    index
+}
+
+/// Most leading characters of `chars` that fit in `max_width`.
+fn count_fitting(chars: &[char], max_width: f32, measure: impl Fn(&str) -> (f32, f32)) -> usize {
+    let (mut low, mut high) = (0, chars.len());
+    while low < high {
+        let mid = (low + high).div_ceil(2);
+        let (width, _) = measure(&chars[..mid].iter().collect::<String>());
+        if width <= max_width + 0.5 {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    low
+}
+
+/// Where a line ending before `raw_end` should break: after the last space,
+/// or at a newline; `raw_end` when there is neither.
+fn break_index(chars: &[char], start: usize, raw_end: usize) -> usize {
+    for index in (start..raw_end).rev() {
+        match chars[index] {
+            ' ' | '\t' => return index + 1,
+            '\n' => return index,
+            _ => {}
+        }
+    }
+    raw_end
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(width: f32, height: f32) -> LineRect {
+        LineRect {
+            x: 0.0,
+            y: 0.0,
+            width,
+            height,
+            foreground_color: String::new(),
+        }
+    }
+
+    #[test]
+    fn breaks_translations_at_spaces_across_line_rects() {
+        let monospace = |line: &str| (line.chars().count() as f32 * 10.0, 12.0);
+        let rects = [rect(100.0, 20.0), rect(100.0, 20.0)];
+
+        let (fits, lines) = fit_lines("hello big world", &rects, monospace);
+        assert!(fits);
+        assert_eq!(lines, ["hello big", "world"]);
+
+        let (fits, lines) = fit_lines("hello big world again", &rects[..1], monospace);
+        assert!(!fits);
+        assert_eq!(lines, ["hello big"]);
+
+        let (fits, _) = fit_lines("hello", &[rect(100.0, 10.0)], monospace);
+        assert!(!fits);
+    }
+}
@@ -1,8 +1,24 @@
+use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufReader, Read, Seek};
 use std::path::{Path, PathBuf};
-use std::time::Instant;
+use std::sync::{LazyLock, Mutex};
+use std::time::{Instant, SystemTime};
 
-use image::{GenericImageView, ImageDecoder, ImageReader, imageops::FilterType};
-use translator::{BackgroundMode, TranslatorSession};
+use image::{
+    DynamicImage, GenericImageView, ImageBuffer, ImageDecoder, ImageError, ImageReader, RgbaImage,
+    imageops::{self, FilterType},
+};
+use translator::TranslatorSession;
+
+use crate::i18n::tr_args;
+use crate::image_preprocess::{Preprocess, SidewaysTurn, preprocess};
+use crate::model::{OcrBackgroundMode, OcrReadingOrder};
+#[cfg(feature = "pdf")]
+use crate::pdf_render::render_pages;
+use crate::placeholders::contains_glossary_term;
+use crate::reading_order::{Rect, is_vertical_text};
+use crate::translation::{self, TranslationOptions};
 
 #[derive(Debug, Clone)]
 pub struct ImageOverlayLine {
@@ -11,6 +27,18 @@ pub struct ImageOverlayLine {
     pub width: u32,
     pub height: u32,
     pub foreground_argb: u32,
+    pub text: String,
+}
+
+impl ImageOverlayLine {
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -21,11 +49,45 @@ pub struct ImageOverlayBlock {
     pub height: u32,
     pub suggested_font_size_px: f32,
     pub lines: Vec<ImageOverlayLine>,
+    pub source_text: String,
     pub translated_text: String,
     pub background_argb: u32,
     pub foreground_argb: u32,
+    /// `LeftToRight`, `RightToLeft` or `Vertical`, as read for this block.
+    pub reading_order: OcrReadingOrder,
+}
+
+impl ImageOverlayBlock {
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x: self.x,
+            y: self.y,
+            width: self.width,
+            height: self.height,
+        }
+    }
 }
 
+/// Regions narrower or shorter than this, in pixels, are ignored.
+const MIN_REGION_SIZE: u32 = 8;
+/// Listed when an image can't be decoded. TIFFs may have several pages, GIFs
+/// are read from their first frame.
+const SUPPORTED_FORMATS: &str = "PNG, JPEG, WebP, TIFF, BMP, GIF, PDF";
+/// Sideways images are read at most this large on either side to choose
+/// which way to turn them.
+const ORIENTATION_CHECK_SIZE: u32 = 800;
+/// Resolution PDF pages are rendered at; enough for body text to OCR well.
+const PDF_RENDER_DPI: u32 = 150;
+
+/// Image file, its modification time and the user's quarter turns.
+type TurnKey = (PathBuf, Option<SystemTime>, u8);
+
+/// Turns chosen for sideways images, so rereading one with other settings
+/// doesn't run the orientation check again.
+static SIDEWAYS_TURNS: LazyLock<Mutex<HashMap<TurnKey, SidewaysTurn>>> =
+    LazyLock::new(Default::default);
+
+#[derive(Clone)]
 pub struct ImageTranslation {
     pub extracted_text: String,
     pub translated_text: String,
@@ -44,8 +106,13 @@ struct LoadedImage {
 pub(crate) fn load_preview_rgba(
     path: &Path,
     max_image_size: u32,
+    corrections: &Preprocess,
 ) -> Result<(Vec<u8>, u32, u32), String> {
-    let loaded = load_image_rgba(path, max_image_size)?;
+    // Sideways text is shown as it is until OCR picks a direction.
+    let key = turn_key(path, corrections);
+    let loaded = load_image_rgba(path, max_image_size, corrections, |_| {
+        cached_turn(&key).unwrap_or(SidewaysTurn::Keep)
+    })?;
     Ok((loaded.rgba_bytes, loaded.width, loaded.height))
 }
 
@@ -69,6 +136,149 @@ pub fn resolve_local_path(input: &str) -> Option<PathBuf> {
     Some(direct)
 }
 
+pub fn is_pdf(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| extension.eq_ignore_ascii_case("pdf"))
+}
+
+/// Whether `path` holds an image or PDF, judged by its contents and then its
+/// extension. Anything else opened from the command line is read as text.
+pub fn is_image_or_document(path: &Path) -> bool {
+    is_pdf(path)
+        || ImageReader::open(path)
+            .and_then(|reader| reader.with_guessed_format())
+            .is_ok_and(|reader| reader.format().is_some())
+}
+
+pub fn is_tiff(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| matches!(extension.to_lowercase().as_str(), "tif" | "tiff"))
+}
+
+/// Renders every page of `pdf` to a PNG in `output_dir` with poppler,
+/// replacing earlier pages, and returns the pages in order.
+pub fn render_pdf_pages(pdf: &Path, output_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    reset_pages_dir(output_dir)?;
+    render_pages(pdf, &output_dir.join("page"), PDF_RENDER_DPI)?;
+
+    // Page numbers are zero-padded to the same width, so names sort in order.
+    let mut pages = std::fs::read_dir(output_dir)
+        .map_err(|e| format!("Failed to read {}: {e}", output_dir.display()))?
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| path.extension().is_some_and(|extension| extension == "png"))
+        .collect::<Vec<_>>();
+    pages.sort();
+    if pages.is_empty() {
+        return Err(format!("No pages found in {}", pdf.display()));
+    }
+    Ok(pages)
+}
+
+#[cfg(not(feature = "pdf"))]
+fn render_pages(pdf: &Path, _prefix: &Path, _dpi: u32) -> Result<usize, String> {
+    Err(format!(
+        "Couldn't open PDF {}: this build has no PDF support",
+        pdf.display()
+    ))
+}
+
+/// Writes each page of a multi-page TIFF to a PNG in `output_dir`, replacing
+/// earlier pages. A single-page TIFF is returned as is.
+pub fn split_tiff_pages(path: &Path, output_dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let tiff_error = |e: tiff::TiffError| format!("Failed to read TIFF {}: {e}", path.display());
+    let file =
+        File::open(path).map_err(|e| format!("Failed to open image {}: {e}", path.display()))?;
+    let mut decoder = tiff::decoder::Decoder::new(BufReader::new(file)).map_err(tiff_error)?;
+    if !decoder.more_images() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    reset_pages_dir(output_dir)?;
+    let mut pages = Vec::new();
+    loop {
+        let page = output_dir.join(format!("page-{:04}.png", pages.len() + 1));
+        tiff_page_image(&mut decoder)
+            .map_err(|message| format!("{message} in {}", path.display()))?
+            .save(&page)
+            .map_err(|e| format!("Failed to write {}: {e}", page.display()))?;
+        pages.push(page);
+        if !decoder.more_images() {
+            break;
+        }
+        decoder.next_image().map_err(tiff_error)?;
+    }
+    Ok(pages)
+}
+
+/// Decodes the decoder's current page. Covers the layouts scanners and
+/// screenshot tools write; others are reported as unsupported.
+fn tiff_page_image<R: Read + Seek>(
+    decoder: &mut tiff::decoder::Decoder<R>,
+) -> Result<DynamicImage, String> {
+    use tiff::ColorType;
+    use tiff::decoder::DecodingResult;
+
+    let (width, height) = decoder.dimensions().map_err(|e| e.to_string())?;
+    let color_type = decoder.colortype().map_err(|e| e.to_string())?;
+    let data = decoder.read_image().map_err(|e| e.to_string())?;
+    let image = match (color_type, data) {
+        (ColorType::Gray(1), DecodingResult::U8(bits)) => {
+            // Rows of bits, each padded to a whole byte. The decoder already
+            // inverts WhiteIsZero pages, so 1 is white in either
+            // PhotometricInterpretation.
+            let row_bytes = width.div_ceil(8) as usize;
+            let pixels = bits
+                .chunks_exact(row_bytes)
+                .flat_map(|row| {
+                    (0..width as usize).map(move |x| {
+                        if row[x / 8] & (0x80 >> (x % 8)) != 0 {
+                            255
+                        } else {
+                            0
+                        }
+                    })
+                })
+                .collect();
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageLuma8)
+        }
+        (ColorType::Gray(8), DecodingResult::U8(pixels)) => {
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageLuma8)
+        }
+        (ColorType::GrayA(8), DecodingResult::U8(pixels)) => {
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageLumaA8)
+        }
+        (ColorType::RGB(8), DecodingResult::U8(pixels)) => {
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgb8)
+        }
+        (ColorType::RGBA(8), DecodingResult::U8(pixels)) => {
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgba8)
+        }
+        (ColorType::Gray(16), DecodingResult::U16(pixels)) => {
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageLuma16)
+        }
+        (ColorType::RGB(16), DecodingResult::U16(pixels)) => {
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgb16)
+        }
+        (ColorType::RGBA(16), DecodingResult::U16(pixels)) => {
+            ImageBuffer::from_raw(width, height, pixels).map(DynamicImage::ImageRgba16)
+        }
+        (color_type, _) => return Err(format!("Unsupported TIFF page format {color_type:?}")),
+    };
+    image.ok_or_else(|| "TIFF page data doesn't match its size".to_string())
+}
+
+fn reset_pages_dir(output_dir: &Path) -> Result<(), String> {
+    if output_dir.exists() {
+        std::fs::remove_dir_all(output_dir)
+            .map_err(|e| format!("Failed to clear {}: {e}", output_dir.display()))?;
+    }
+    std::fs::create_dir_all(output_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", output_dir.display()))
+}
+
+#[allow(clippy::too_many_arguments)]
 pub fn translate_image_with_session(
     session: &TranslatorSession,
     image_path: &Path,
@@ -76,59 +286,197 @@ pub fn translate_image_with_session(
     target_code: &str,
     min_confidence: u32,
     max_image_size: u32,
-    background_mode_label: &str,
+    background_mode: OcrBackgroundMode,
+    reading_order: OcrReadingOrder,
+    ocr_only: bool,
+    regions: &[Rect],
+    corrections: &Preprocess,
+    options: &TranslationOptions,
 ) -> Result<ImageTranslation, String> {
     let total_start = Instant::now();
     let load_start = Instant::now();
-    let loaded = load_image_rgba(image_path, max_image_size)?;
+    let key = turn_key(image_path, corrections);
+    let loaded = load_image_rgba(image_path, max_image_size, corrections, |image| {
+        if let Some(turn) = cached_turn(&key) {
+            return turn;
+        }
+        let turn = choose_sideways_turn(session, image, source_code, min_confidence, reading_order);
+        SIDEWAYS_TURNS.lock().unwrap().insert(key, turn);
+        turn
+    })?;
     let load_elapsed = load_start.elapsed();
-    let background_mode = map_background_mode(background_mode_label);
+    let whole = Rect {
+        x: 0,
+        y: 0,
+        width: loaded.width,
+        height: loaded.height,
+    };
+    let regions = clamp_regions(regions, whole);
     let process_start = Instant::now();
-    let prepared = session
-        .translate_image_rgba(
-            &loaded.rgba_bytes,
-            loaded.width,
-            loaded.height,
-            source_code,
-            target_code,
-            min_confidence,
-            translator::ReadingOrder::LeftToRight,
-            background_mode,
-        )
+    let mut prepared_regions = Vec::with_capacity(regions.len());
+    for region in regions {
+        let cropped;
+        let rgba_bytes = if region == whole {
+            &loaded.rgba_bytes
+        } else {
+            cropped = crop_rgba(&loaded.rgba_bytes, loaded.width, region);
+            &cropped
+        };
+        // Text extraction only needs the source language's OCR data, and
+        // leaves the image uncleaned.
+        let prepared = if ocr_only {
+            session.recognize_image_rgba(
+                rgba_bytes,
+                region.width,
+                region.height,
+                source_code,
+                min_confidence,
+                reading_order.into(),
+            )
+        } else {
+            session.translate_image_rgba(
+                rgba_bytes,
+                region.width,
+                region.height,
+                source_code,
+                target_code,
+                min_confidence,
+                reading_order.into(),
+                background_mode.into(),
+            )
+        }
         .map_err(|err| {
-            if err.is_missing_asset() {
-                format!("Missing installed language pair {source_code}->{target_code}")
+            if err.is_missing_asset() && ocr_only {
+                tr_args(
+                    "Missing OCR data for {language}",
+                    &[("language", source_code)],
+                )
+            } else if err.is_missing_asset() {
+                tr_args(
+                    "Missing installed language pair {from}->{to}",
+                    &[("from", source_code), ("to", target_code)],
+                )
             } else {
                 err.message
             }
         })?;
+        prepared_regions.push((region, prepared));
+    }
     let process_elapsed = process_start.elapsed();
 
-    let overlay_blocks = prepared
-        .blocks
-        .into_iter()
-        .map(|block| ImageOverlayBlock {
-            suggested_font_size_px: block.layout_hints.suggested_font_size_px,
-            lines: block
+    // The engine orders blocks and lines for `reading_order`; region
+    // coordinates are offset back into the full image.
+    let mut retranslate = Vec::new();
+    let mut engine_extracted = Vec::new();
+    let mut engine_translated = Vec::new();
+    let mut cleaned_whole = None;
+    let mut cleaned_regions = Vec::new();
+    let mut overlay_blocks = Vec::new();
+    for (region, prepared) in prepared_regions {
+        engine_extracted.push(prepared.extracted_text);
+        engine_translated.push(prepared.translated_text);
+        // Extracted text stays visible on the original image.
+        if !ocr_only {
+            if region == whole {
+                cleaned_whole = Some(prepared.rgba_bytes);
+            } else {
+                cleaned_regions.push((region, prepared.rgba_bytes));
+            }
+        }
+        overlay_blocks.extend(prepared.blocks.into_iter().map(|block| {
+            let lines = block
                 .lines
                 .iter()
                 .map(|line| ImageOverlayLine {
-                    x: line.bounding_box.left,
-                    y: line.bounding_box.top,
+                    x: region.x + line.bounding_box.left,
+                    y: region.y + line.bounding_box.top,
                     width: line.bounding_box.width(),
                     height: line.bounding_box.height(),
                     foreground_argb: line.foreground_argb,
+                    text: line.text.trim().to_string(),
                 })
-                .collect(),
-            x: block.bounding_box.left,
-            y: block.bounding_box.top,
-            width: block.bounding_box.width(),
-            height: block.bounding_box.height(),
-            translated_text: block.translated_text,
-            background_argb: block.background_argb,
-            foreground_argb: block.foreground_argb,
-        })
-        .collect::<Vec<_>>();
+                .collect::<Vec<_>>();
+            // Horizontal blocks on a vertical page are laid out as rows.
+            let block_order = match reading_order {
+                OcrReadingOrder::Vertical
+                    if !is_vertical_text(
+                        &lines.iter().map(ImageOverlayLine::rect).collect::<Vec<_>>(),
+                    ) =>
+                {
+                    OcrReadingOrder::LeftToRight
+                }
+                OcrReadingOrder::Automatic => OcrReadingOrder::LeftToRight,
+                order => order,
+            };
+            // Vertical CJK columns continue the same sentence without spaces.
+            let separator = if block_order == OcrReadingOrder::Vertical {
+                ""
+            } else {
+                " "
+            };
+            let source_text = join_line_text(&lines, separator);
+            // The engine's own translation stands unless a glossary term
+            // needs enforcing.
+            retranslate.push(contains_glossary_term(&source_text, &options.glossary));
+
+            ImageOverlayBlock {
+                suggested_font_size_px: block.layout_hints.suggested_font_size_px,
+                lines,
+                source_text,
+                x: region.x + block.bounding_box.left,
+                y: region.y + block.bounding_box.top,
+                width: block.bounding_box.width(),
+                height: block.bounding_box.height(),
+                translated_text: if ocr_only {
+                    String::new()
+                } else {
+                    block.translated_text
+                },
+                background_argb: block.background_argb,
+                foreground_argb: block.foreground_argb,
+                reading_order: block_order,
+            }
+        }));
+    }
+
+    let retranslated = !ocr_only && retranslate.iter().any(|changed| *changed);
+    if retranslated {
+        // The session translates blocks internally, so glossary terms are
+        // applied by re-translating just the blocks that contain them.
+        let retranslate_start = Instant::now();
+        for (block, changed) in overlay_blocks.iter_mut().zip(retranslate) {
+            if !changed {
+                continue;
+            }
+            match translation::translate_text(
+                session,
+                source_code,
+                target_code,
+                &block.source_text,
+                options,
+            ) {
+                Ok(output) => block.translated_text = output.text,
+                Err(err) => eprintln!("image_ocr block retranslation failed: {err}"),
+            }
+        }
+        println!(
+            "image_ocr retranslation took {:?}",
+            retranslate_start.elapsed()
+        );
+    }
+
+    let extracted_text = engine_extracted.join("\n");
+    let translated_text = if ocr_only {
+        String::new()
+    } else if retranslated {
+        overlay_blocks
+            .iter()
+            .map(|block| block.translated_text.as_str())
+            .collect::<Vec<_>>()
+            .join("\n")
+    } else {
+        engine_translated.join("\n")
+    };
 
     println!(
         "image_ocr timings load={:?} process={:?} total={:?}",
@@ -138,33 +486,164 @@ pub fn translate_image_with_session(
     );
 
     Ok(ImageTranslation {
-        extracted_text: prepared.extracted_text,
-        translated_text: prepared.translated_text,
+        extracted_text,
+        translated_text,
         image_width: loaded.width,
         image_height: loaded.height,
-        cleaned_rgba_bytes: prepared.rgba_bytes,
+        cleaned_rgba_bytes: cleaned_whole.unwrap_or_else(|| {
+            let mut rgba_bytes = loaded.rgba_bytes;
+            for (region, cleaned) in cleaned_regions {
+                paste_rgba(&mut rgba_bytes, loaded.width, region, &cleaned);
+            }
+            rgba_bytes
+        }),
         overlay_blocks,
     })
 }
 
-fn load_image_rgba(path: &Path, max_image_size: u32) -> Result<LoadedImage, String> {
+/// Clips `regions` to the image and drops slivers too small to hold text; no
+/// usable region means the whole image.
+fn clamp_regions(regions: &[Rect], image: Rect) -> Vec<Rect> {
+    let clamped = regions
+        .iter()
+        .filter_map(|region| {
+            let x = region.x.min(image.width);
+            let y = region.y.min(image.height);
+            let width = region.width.min(image.width - x);
+            let height = region.height.min(image.height - y);
+            (width >= MIN_REGION_SIZE && height >= MIN_REGION_SIZE).then_some(Rect {
+                x,
+                y,
+                width,
+                height,
+            })
+        })
+        .collect::<Vec<_>>();
+    if clamped.is_empty() {
+        vec![image]
+    } else {
+        clamped
+    }
+}
+
+fn crop_rgba(rgba_bytes: &[u8], width: u32, region: Rect) -> Vec<u8> {
+    let stride = width as usize * 4;
+    let row_length = region.width as usize * 4;
+    (region.y..region.y + region.height)
+        .flat_map(|y| {
+            let start = y as usize * stride + region.x as usize * 4;
+            rgba_bytes[start..start + row_length].iter().copied()
+        })
+        .collect()
+}
+
+fn paste_rgba(rgba_bytes: &mut [u8], width: u32, region: Rect, pixels: &[u8]) {
+    let stride = width as usize * 4;
+    let row_length = region.width as usize * 4;
+    if pixels.len() != row_length * region.height as usize {
+        return;
+    }
+    for (row, source) in pixels.chunks_exact(row_length).enumerate() {
+        let start = (region.y as usize + row) * stride + region.x as usize * 4;
+        rgba_bytes[start..start + row_length].copy_from_slice(source);
+    }
+}
+
+fn join_line_text(lines: &[ImageOverlayLine], separator: &str) -> String {
+    lines
+        .iter()
+        .map(|line| line.text.as_str())
+        .filter(|text| !text.is_empty())
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+fn turn_key(path: &Path, corrections: &Preprocess) -> TurnKey {
+    let modified = std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok();
+    (path.to_path_buf(), modified, corrections.quarter_turns % 4)
+}
+
+fn cached_turn(key: &TurnKey) -> Option<SidewaysTurn> {
+    SIDEWAYS_TURNS.lock().unwrap().get(key).copied()
+}
+
+/// Sideways text reads upright in only one of the two turns, and vertical
+/// columns read best as they are, so whichever yields the most confidently
+/// recognized text wins. A turn has to beat the image as it is.
+fn choose_sideways_turn(
+    session: &TranslatorSession,
+    image: &RgbaImage,
+    source_code: &str,
+    min_confidence: u32,
+    reading_order: OcrReadingOrder,
+) -> SidewaysTurn {
+    let (width, height) = scaled_dimensions(image.width(), image.height(), ORIENTATION_CHECK_SIZE);
+    let small = imageops::thumbnail(image, width, height);
+    let recognized = |image: &RgbaImage| {
+        recognized_chars(session, image, source_code, min_confidence, reading_order)
+    };
+    let kept = recognized(&small);
+    let clockwise = recognized(&imageops::rotate90(&small));
+    let counterclockwise = recognized(&imageops::rotate270(&small));
+    if counterclockwise > clockwise.max(kept) {
+        SidewaysTurn::Counterclockwise
+    } else if clockwise > kept {
+        SidewaysTurn::Clockwise
+    } else {
+        SidewaysTurn::Keep
+    }
+}
+
+/// Characters OCR reads from `image`; the engine drops lines below
+/// `min_confidence`, so text read upside down or sideways scores low.
+fn recognized_chars(
+    session: &TranslatorSession,
+    image: &RgbaImage,
+    source_code: &str,
+    min_confidence: u32,
+    reading_order: OcrReadingOrder,
+) -> usize {
+    session
+        .recognize_image_rgba(
+            image.as_raw(),
+            image.width(),
+            image.height(),
+            source_code,
+            min_confidence,
+            reading_order.into(),
+        )
+        .map(|prepared| {
+            prepared
+                .extracted_text
+                .chars()
+                .filter(|c| !c.is_whitespace())
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+fn load_image_rgba(
+    path: &Path,
+    max_image_size: u32,
+    corrections: &Preprocess,
+    choose_turn: impl FnOnce(&RgbaImage) -> SidewaysTurn,
+) -> Result<LoadedImage, String> {
+    // The contents decide the format, so misnamed files still open.
     let mut decoder = ImageReader::open(path)
+        .and_then(ImageReader::with_guessed_format)
         .map_err(|err| format!("Failed to open image {}: {err}", path.display()))?
         .into_decoder()
-        .map_err(|err| {
-            format!(
-                "Failed to create decoder for image {}: {err}",
-                path.display()
-            )
-        })?;
+        .map_err(|err| unsupported_image(path, err))?;
     let orientation = decoder.orientation().map_err(|err| {
         format!(
             "Failed to read orientation for image {}: {err}",
             path.display()
         )
     })?;
-    let mut image = image::DynamicImage::from_decoder(decoder)
-        .map_err(|err| format!("Failed to decode image {}: {err}", path.display()))?;
+    let mut image =
+        DynamicImage::from_decoder(decoder).map_err(|err| unsupported_image(path, err))?;
     image.apply_orientation(orientation);
 
     let (source_width, source_height) = image.dimensions();
@@ -187,14 +666,22 @@ fn load_image_rgba(path: &Path, max_image_size: u32) -> Result<LoadedImage, Stri
             .resize_exact(width, height, FilterType::Triangle)
             .to_rgba8()
     };
+    let rgba = preprocess(rgba, corrections, choose_turn);
 
     Ok(LoadedImage {
+        width: rgba.width(),
+        height: rgba.height(),
         rgba_bytes: rgba.into_raw(),
-        width,
-        height,
     })
 }
 
+fn unsupported_image(path: &Path, err: ImageError) -> String {
+    format!(
+        "Couldn't read image {}: {err}. Supported formats: {SUPPORTED_FORMATS}",
+        path.display()
+    )
+}
+
 fn percent_decode(value: &str) -> String {
     let bytes = value.as_bytes();
     let mut decoded = Vec::with_capacity(bytes.len());
@@ -236,10 +723,112 @@ fn scaled_dimensions(width: u32, height: u32, max_image_size: u32) -> (u32, u32)
     }
 }
 
-fn map_background_mode(label: &str) -> BackgroundMode {
-    match label {
-        "Light Background" => BackgroundMode::BlackOnWhite,
-        "Dark Background" => BackgroundMode::WhiteOnBlack,
-        _ => BackgroundMode::AutoDetect,
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crops_and_pastes_clamped_regions() {
+        let image = Rect {
+            x: 0,
+            y: 0,
+            width: 20,
+            height: 10,
+        };
+        let regions = clamp_regions(
+            &[
+                Rect {
+                    x: 12,
+                    y: 2,
+                    width: 40,
+                    height: 8,
+                },
+                Rect {
+                    x: 0,
+                    y: 0,
+                    width: 4,
+                    height: 4,
+                },
+            ],
+            image,
+        );
+        let region = Rect {
+            x: 12,
+            y: 2,
+            width: 8,
+            height: 8,
+        };
+        assert_eq!(regions, [region]);
+        assert_eq!(clamp_regions(&[], image), [image]);
+
+        let mut rgba_bytes = (0..20 * 10)
+            .flat_map(|pixel| [pixel as u8; 4])
+            .collect::<Vec<_>>();
+        let cropped = crop_rgba(&rgba_bytes, 20, region);
+        assert_eq!(cropped.len(), 8 * 8 * 4);
+        assert_eq!(cropped[..4], [52; 4]);
+
+        paste_rgba(&mut rgba_bytes, 20, region, &vec![255; cropped.len()]);
+        assert_eq!(rgba_bytes[(2 * 20 + 12) * 4], 255);
+        assert_eq!(rgba_bytes[(2 * 20 + 11) * 4], 51);
+    }
+
+    #[test]
+    fn splits_multi_page_tiffs_into_pngs() {
+        use tiff::encoder::{TiffEncoder, colortype};
+
+        let dir = std::env::temp_dir().join(format!("translator-tiff-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let tiff_path = dir.join("scan.tif");
+        let mut encoder = TiffEncoder::new(File::create(&tiff_path).unwrap()).unwrap();
+        encoder
+            .write_image::<colortype::Gray8>(4, 2, &[0, 50, 100, 150, 200, 250, 255, 0])
+            .unwrap();
+        encoder
+            .write_image::<colortype::RGB8>(2, 1, &[255, 0, 0, 0, 0, 255])
+            .unwrap();
+        drop(encoder);
+
+        let pages = split_tiff_pages(&tiff_path, &dir.join("pages")).unwrap();
+        assert_eq!(pages.len(), 2);
+        let first = image::open(&pages[0]).unwrap().to_luma8();
+        assert_eq!(first.dimensions(), (4, 2));
+        assert_eq!(first.get_pixel(1, 0).0, [50]);
+        let second =
+            load_image_rgba(&pages[1], 0, &Preprocess::default(), |_| SidewaysTurn::Keep).unwrap();
+        assert_eq!(second.rgba_bytes, [255, 0, 0, 255, 0, 0, 255, 255]);
+
+        let not_an_image = dir.join("notes.png");
+        std::fs::write(&not_an_image, "plain text").unwrap();
+        let error = load_image_rgba(&not_an_image, 0, &Preprocess::default(), |_| {
+            SidewaysTurn::Keep
+        })
+        .err()
+        .unwrap();
+        assert!(error.contains("Supported formats: PNG, JPEG, WebP"));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn reads_one_bit_scans_in_both_photometric_interpretations() {
+        // 8x1 pixels with the left half set; WhiteIsZero (0) draws set bits
+        // black, BlackIsZero (1) white.
+        for (photometric, left) in [(0u32, 0u8), (1, 255)] {
+            let mut scan = b"II*\0\x08\0\0\0\x08\0".to_vec();
+            for (tag, value) in [(256u16, 8u32), (257, 1), (258, 1), (259, 1)]
+                .into_iter()
+                .chain([(262, photometric), (273, 110), (278, 1), (279, 1)])
+            {
+                scan.extend(tag.to_le_bytes());
+                scan.extend(3u16.to_le_bytes());
+                scan.extend(1u32.to_le_bytes());
+                scan.extend(value.to_le_bytes());
+            }
+            scan.extend([0, 0, 0, 0, 0b1111_0000]);
+            let mut decoder = tiff::decoder::Decoder::new(std::io::Cursor::new(scan)).unwrap();
+            let page = tiff_page_image(&mut decoder).unwrap().to_luma8();
+            assert_eq!(page.get_pixel(0, 0).0, [left]);
+            assert_eq!(page.get_pixel(7, 0).0, [255 - left]);
+        }
     }
 }
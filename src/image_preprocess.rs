@@ -0,0 +1,368 @@
+use image::{Rgba, RgbaImage, imageops};
+
+/// Corrections applied to a photo before OCR. Overlay and region coordinates
+/// refer to the corrected image.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Preprocess {
+    /// Clockwise quarter turns chosen by the user.
+    pub quarter_turns: u8,
+    /// Turn text that runs sideways upright.
+    pub detect_sideways: bool,
+    /// Straighten text lines tilted by a few degrees.
+    pub deskew: bool,
+    /// Page corners (top-left, top-right, bottom-right, bottom-left) in pixels
+    /// of the turned image, stretched to a rectangle. Replaces deskewing.
+    pub perspective: Option<[(f32, f32); 4]>,
+}
+
+/// Largest tilt, in degrees, that deskewing corrects.
+const MAX_SKEW_DEGREES: f32 = 10.0;
+const SKEW_STEP_DEGREES: f32 = 0.25;
+/// A tilt must sharpen the text-line profile by this factor to be corrected;
+/// smaller gains come from noise on pages that are already level.
+const SKEW_MIN_GAIN: f64 = 1.15;
+/// Column profiles must be this much sharper than row profiles to turn text.
+const SIDEWAYS_MIN_GAIN: f64 = 1.25;
+/// Analysis runs on a copy no larger than this on either side.
+const ANALYSIS_SIZE: u32 = 400;
+/// Luminance distance from the background that counts as ink.
+const INK_CONTRAST: i32 = 48;
+/// Fewer ink pixels than this leave the image as it is.
+const MIN_INK_POINTS: usize = 200;
+
+/// How to turn an image whose text looks sideways.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SidewaysTurn {
+    /// Columns of text, such as vertical CJK, that only look sideways.
+    Keep,
+    Clockwise,
+    Counterclockwise,
+}
+
+/// Text that looks sideways could read upright either way or not need turning
+/// at all; `choose_turn` decides from the image before it's turned.
+pub fn preprocess(
+    mut image: RgbaImage,
+    options: &Preprocess,
+    choose_turn: impl FnOnce(&RgbaImage) -> SidewaysTurn,
+) -> RgbaImage {
+    for _ in 0..options.quarter_turns % 4 {
+        image = imageops::rotate90(&image);
+    }
+    if options.detect_sideways && is_sideways(&ink_points(&image)) {
+        match choose_turn(&image) {
+            SidewaysTurn::Keep => {}
+            SidewaysTurn::Clockwise => image = imageops::rotate90(&image),
+            SidewaysTurn::Counterclockwise => image = imageops::rotate270(&image),
+        }
+    }
+    if let Some(corners) = options.perspective {
+        return correct_perspective(&image, corners);
+    }
+    if options.deskew {
+        let angle = skew_degrees(&ink_points(&image));
+        if angle != 0.0 {
+            image = rotate_degrees(&image, angle);
+        }
+    }
+    image
+}
+
+/// Orders four tapped points as top-left, top-right, bottom-right,
+/// bottom-left.
+pub fn order_corners(points: [(f32, f32); 4]) -> [(f32, f32); 4] {
+    let pick = |key: fn(&(f32, f32)) -> f32, largest: bool| {
+        let mut sorted = points;
+        sorted.sort_by(|a, b| key(a).total_cmp(&key(b)));
+        if largest { sorted[3] } else { sorted[0] }
+    };
+    [
+        pick(|(x, y)| x + y, false),
+        pick(|(x, y)| x - y, true),
+        pick(|(x, y)| x + y, true),
+        pick(|(x, y)| x - y, false),
+    ]
+}
+
+/// Dark-or-light pixels standing out from the background, in coordinates of
+/// a downscaled copy.
+fn ink_points(image: &RgbaImage) -> Vec<(f32, f32)> {
+    let (width, height) = image.dimensions();
+    let scale = (ANALYSIS_SIZE as f32 / width.max(height) as f32).min(1.0);
+    let small = if scale < 1.0 {
+        imageops::thumbnail(
+            image,
+            ((width as f32 * scale) as u32).max(1),
+            ((height as f32 * scale) as u32).max(1),
+        )
+    } else {
+        image.clone()
+    };
+
+    let luminance = |pixel: &Rgba<u8>| {
+        (pixel[0] as i32 * 299 + pixel[1] as i32 * 587 + pixel[2] as i32 * 114) / 1000
+    };
+    let mut histogram = [0usize; 256];
+    for pixel in small.pixels() {
+        histogram[luminance(pixel) as usize] += 1;
+    }
+    let half = small.pixels().len() / 2;
+    let mut seen = 0;
+    let background = histogram
+        .iter()
+        .position(|count| {
+            seen += count;
+            seen > half
+        })
+        .unwrap_or(0) as i32;
+
+    small
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| (luminance(pixel) - background).abs() > INK_CONTRAST)
+        .map(|(x, y, _)| (x as f32, y as f32))
+        .collect()
+}
+
+/// How much `values` pile up in a few one-pixel bins, relative to an even
+/// spread; text lines crossing the bins make this large.
+fn profile_sharpness(values: impl Iterator<Item = f32>) -> f64 {
+    let values = values.collect::<Vec<_>>();
+    let min = values.iter().copied().fold(f32::INFINITY, f32::min);
+    let max = values.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    if values.is_empty() || !min.is_finite() || !max.is_finite() {
+        return 0.0;
+    }
+    let mut bins = vec![0u64; (max - min) as usize + 1];
+    for value in &values {
+        bins[(value - min) as usize] += 1;
+    }
+    let total = values.len() as f64;
+    let squares = bins.iter().map(|count| (count * count) as f64).sum::<f64>();
+    squares * bins.len() as f64 / (total * total)
+}
+
+fn is_sideways(points: &[(f32, f32)]) -> bool {
+    if points.len() < MIN_INK_POINTS {
+        return false;
+    }
+    let rows = profile_sharpness(points.iter().map(|(_, y)| *y));
+    let columns = profile_sharpness(points.iter().map(|(x, _)| *x));
+    columns > rows * SIDEWAYS_MIN_GAIN
+}
+
+/// Clockwise rotation, in degrees, that makes the text lines horizontal.
+fn skew_degrees(points: &[(f32, f32)]) -> f32 {
+    if points.len() < MIN_INK_POINTS {
+        return 0.0;
+    }
+    let sharpness = |degrees: f32| {
+        let (sin, cos) = degrees.to_radians().sin_cos();
+        profile_sharpness(points.iter().map(|(x, y)| y * cos + x * sin))
+    };
+    let level = sharpness(0.0);
+    let steps = (MAX_SKEW_DEGREES / SKEW_STEP_DEGREES) as i32;
+    let (degrees, best) = (-steps..=steps)
+        .map(|step| step as f32 * SKEW_STEP_DEGREES)
+        .map(|degrees| (degrees, sharpness(degrees)))
+        .fold((0.0, level), |best, candidate| {
+            if candidate.1 > best.1 {
+                candidate
+            } else {
+                best
+            }
+        });
+    if best > level * SKEW_MIN_GAIN {
+        degrees
+    } else {
+        0.0
+    }
+}
+
+/// Rotates clockwise by `degrees` about the center, keeping the size and
+/// filling uncovered corners with the border color.
+fn rotate_degrees(image: &RgbaImage, degrees: f32) -> RgbaImage {
+    let (sin, cos) = degrees.to_radians().sin_cos();
+    let center_x = image.width() as f32 / 2.0;
+    let center_y = image.height() as f32 / 2.0;
+    let fill = border_color(image);
+    RgbaImage::from_fn(image.width(), image.height(), |x, y| {
+        let dx = x as f32 + 0.5 - center_x;
+        let dy = y as f32 + 0.5 - center_y;
+        let source_x = center_x + dx * cos + dy * sin - 0.5;
+        let source_y = center_y - dx * sin + dy * cos - 0.5;
+        sample_bilinear(image, source_x, source_y).unwrap_or(fill)
+    })
+}
+
+fn correct_perspective(image: &RgbaImage, corners: [(f32, f32); 4]) -> RgbaImage {
+    let distance = |a: (f32, f32), b: (f32, f32)| (a.0 - b.0).hypot(a.1 - b.1);
+    let [top_left, top_right, bottom_right, bottom_left] = corners;
+    let width = distance(top_left, top_right)
+        .max(distance(bottom_left, bottom_right))
+        .round()
+        .max(1.0);
+    let height = distance(top_left, bottom_left)
+        .max(distance(top_right, bottom_right))
+        .round()
+        .max(1.0);
+    let rectangle = [(0.0, 0.0), (width, 0.0), (width, height), (0.0, height)];
+    let Some(matrix) = homography(rectangle, corners) else {
+        eprintln!("image_preprocess: perspective corners are degenerate");
+        return image.clone();
+    };
+
+    let fill = border_color(image);
+    RgbaImage::from_fn(width as u32, height as u32, |x, y| {
+        let (source_x, source_y) = project(&matrix, x as f32, y as f32);
+        sample_bilinear(image, source_x, source_y).unwrap_or(fill)
+    })
+}
+
+/// The projective transform taking each `from` point to the matching `to`
+/// point, as the first eight entries of a 3x3 matrix whose last entry is 1.
+fn homography(from: [(f32, f32); 4], to: [(f32, f32); 4]) -> Option<[f64; 8]> {
+    let mut rows = [[0.0f64; 9]; 8];
+    for (index, ((x, y), (u, v))) in from.into_iter().zip(to).enumerate() {
+        let (x, y, u, v) = (x as f64, y as f64, u as f64, v as f64);
+        rows[index * 2] = [x, y, 1.0, 0.0, 0.0, 0.0, -u * x, -u * y, u];
+        rows[index * 2 + 1] = [0.0, 0.0, 0.0, x, y, 1.0, -v * x, -v * y, v];
+    }
+
+    // Gaussian elimination with partial pivoting.
+    for column in 0..8 {
+        let pivot =
+            (column..8).max_by(|&a, &b| rows[a][column].abs().total_cmp(&rows[b][column].abs()))?;
+        if rows[pivot][column].abs() < 1e-9 {
+            return None;
+        }
+        rows.swap(column, pivot);
+        let pivot_row = rows[column];
+        for (index, row) in rows.iter_mut().enumerate() {
+            if index != column {
+                let factor = row[column] / pivot_row[column];
+                for (entry, pivot_entry) in row.iter_mut().zip(pivot_row).skip(column) {
+                    *entry -= factor * pivot_entry;
+                }
+            }
+        }
+    }
+    Some(std::array::from_fn(|index| {
+        rows[index][8] / rows[index][index]
+    }))
+}
+
+fn project(matrix: &[f64; 8], x: f32, y: f32) -> (f32, f32) {
+    let (x, y) = (x as f64, y as f64);
+    let w = matrix[6] * x + matrix[7] * y + 1.0;
+    (
+        ((matrix[0] * x + matrix[1] * y + matrix[2]) / w) as f32,
+        ((matrix[3] * x + matrix[4] * y + matrix[5]) / w) as f32,
+    )
+}
+
+fn sample_bilinear(image: &RgbaImage, x: f32, y: f32) -> Option<Rgba<u8>> {
+    let (width, height) = image.dimensions();
+    if !(x >= 0.0 && y >= 0.0 && x <= (width - 1) as f32 && y <= (height - 1) as f32) {
+        return None;
+    }
+    let (x0, y0) = (x.floor() as u32, y.floor() as u32);
+    let (x1, y1) = ((x0 + 1).min(width - 1), (y0 + 1).min(height - 1));
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+    let corners = [
+        image.get_pixel(x0, y0),
+        image.get_pixel(x1, y0),
+        image.get_pixel(x0, y1),
+        image.get_pixel(x1, y1),
+    ];
+    Some(Rgba(std::array::from_fn(|channel| {
+        let top = corners[0][channel] as f32 * (1.0 - fx) + corners[1][channel] as f32 * fx;
+        let bottom = corners[2][channel] as f32 * (1.0 - fx) + corners[3][channel] as f32 * fx;
+        (top * (1.0 - fy) + bottom * fy).round() as u8
+    })))
+}
+
+/// Per-channel median of the outermost pixels, usually the paper or wall
+/// around the text.
+fn border_color(image: &RgbaImage) -> Rgba<u8> {
+    let (width, height) = image.dimensions();
+    let mut border = (0..width)
+        .flat_map(|x| [(x, 0), (x, height - 1)])
+        .chain((0..height).flat_map(|y| [(0, y), (width - 1, y)]))
+        .map(|(x, y)| *image.get_pixel(x, y))
+        .collect::<Vec<_>>();
+    Rgba(std::array::from_fn(|channel| {
+        border.sort_by_key(|pixel| pixel[channel]);
+        border[border.len() / 2][channel]
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// White page with dark horizontal text lines, rotated clockwise by
+    /// `degrees`.
+    fn page(degrees: f32) -> RgbaImage {
+        let white = Rgba([255, 255, 255, 255]);
+        let text = RgbaImage::from_fn(300, 200, |x, y| {
+            if (40..260).contains(&x) && y % 24 < 6 && (30..170).contains(&y) {
+                Rgba([20, 20, 20, 255])
+            } else {
+                white
+            }
+        });
+        rotate_degrees(&text, degrees)
+    }
+
+    #[test]
+    fn straightens_tilted_and_sideways_text() {
+        let tilted = page(4.0);
+        let angle = skew_degrees(&ink_points(&tilted));
+        assert!((angle + 4.0).abs() <= 0.5, "angle {angle}");
+        let straightened = preprocess(
+            tilted,
+            &Preprocess {
+                deskew: true,
+                ..Preprocess::default()
+            },
+            |_| SidewaysTurn::Keep,
+        );
+        assert_eq!(skew_degrees(&ink_points(&straightened)), 0.0);
+
+        let upright = page(0.0);
+        assert!(!is_sideways(&ink_points(&upright)));
+        let sideways = imageops::rotate90(&upright);
+        assert!(is_sideways(&ink_points(&sideways)));
+        let turn_upright = |turn: SidewaysTurn| {
+            preprocess(
+                sideways.clone(),
+                &Preprocess {
+                    detect_sideways: true,
+                    ..Preprocess::default()
+                },
+                |_| turn,
+            )
+        };
+        assert_eq!(turn_upright(SidewaysTurn::Counterclockwise), upright);
+        assert_eq!(
+            turn_upright(SidewaysTurn::Clockwise),
+            imageops::rotate180(&upright)
+        );
+        assert_eq!(turn_upright(SidewaysTurn::Keep), sideways);
+    }
+
+    #[test]
+    fn maps_corners_to_a_rectangle() {
+        let corners = order_corners([(90.0, 10.0), (10.0, 90.0), (15.0, 5.0), (95.0, 80.0)]);
+        assert_eq!(
+            corners,
+            [(15.0, 5.0), (90.0, 10.0), (95.0, 80.0), (10.0, 90.0)]
+        );
+        let rectangle = [(0.0, 0.0), (80.0, 0.0), (80.0, 85.0), (0.0, 85.0)];
+        let matrix = homography(rectangle, corners).unwrap();
+        for (point, corner) in rectangle.into_iter().zip(corners) {
+            let (x, y) = project(&matrix, point.0, point.1);
+            assert!((x - corner.0).abs() < 0.01 && (y - corner.1).abs() < 0.01);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+/// Native name and search aliases for a language code.
+struct LanguageNames {
+    code: &'static str,
+    endonym: &'static str,
+    aliases: &'static [&'static str],
+}
+
+const fn names(
+    code: &'static str,
+    endonym: &'static str,
+    aliases: &'static [&'static str],
+) -> LanguageNames {
+    LanguageNames {
+        code,
+        endonym,
+        aliases,
+    }
+}
+
+/// Aliases hold ISO 639-2/3 codes and alternative English names.
+const LANGUAGE_NAMES: &[LanguageNames] = &[
+    names("af", "Afrikaans", &["afr"]),
+    names("ar", "العربية", &["ara", "arabi"]),
+    names("az", "Azərbaycanca", &["aze", "azeri"]),
+    names("be", "Беларуская", &["bel", "belarusian", "byelorussian"]),
+    names("bg", "Български", &["bul", "bulgarski"]),
+    names("bn", "বাংলা", &["ben", "bangla"]),
+    names("bs", "Bosanski", &["bos"]),
+    names("ca", "Català", &["cat", "valencian"]),
+    names("cs", "Čeština", &["ces", "cze", "czech", "cestina"]),
+    names("cy", "Cymraeg", &["cym", "wel"]),
+    names("da", "Dansk", &["dan"]),
+    names("de", "Deutsch", &["deu", "ger"]),
+    names("el", "Ελληνικά", &["ell", "gre", "greek", "ellinika"]),
+    names("en", "English", &["eng"]),
+    names(
+        "es",
+        "Español",
+        &["spa", "castellano", "castilian", "espanol"],
+    ),
+    names("et", "Eesti", &["est"]),
+    names("eu", "Euskara", &["eus", "baq"]),
+    names("fa", "فارسی", &["fas", "per", "persian", "farsi"]),
+    names("fi", "Suomi", &["fin"]),
+    names("fr", "Français", &["fra", "fre", "francais"]),
+    names("ga", "Gaeilge", &["gle", "irish"]),
+    names("gl", "Galego", &["glg"]),
+    names("gu", "ગુજરાતી", &["guj"]),
+    names("he", "עברית", &["heb", "ivrit"]),
+    names("hi", "हिन्दी", &["hin"]),
+    names("hr", "Hrvatski", &["hrv"]),
+    names("hu", "Magyar", &["hun"]),
+    names("id", "Bahasa Indonesia", &["ind", "indonesian"]),
+    names("is", "Íslenska", &["isl", "ice", "islenska"]),
+    names("it", "Italiano", &["ita"]),
+    names("ja", "日本語", &["jpn", "nihongo"]),
+    names("kk", "Қазақ тілі", &["kaz", "kazakh"]),
+    names("kn", "ಕನ್ನಡ", &["kan"]),
+    names("ko", "한국어", &["kor", "hangul", "hangugeo"]),
+    names("lt", "Lietuvių", &["lit"]),
+    names("lv", "Latviešu", &["lav", "lettish"]),
+    names("mk", "Македонски", &["mkd", "mac"]),
+    names("ml", "മലയാളം", &["mal"]),
+    names("mr", "मराठी", &["mar"]),
+    names("ms", "Bahasa Melayu", &["msa", "may", "malay"]),
+    names("mt", "Malti", &["mlt"]),
+    names("nb", "Norsk bokmål", &["nob", "nor", "norwegian", "bokmal"]),
+    names(
+        "nl",
+        "Nederlands",
+        &["nld", "dut", "dutch", "flemish", "vlaams"],
+    ),
+    names("nn", "Norsk nynorsk", &["nno", "nynorsk"]),
+    names("pl", "Polski", &["pol"]),
+    names("pt", "Português", &["por", "portugues"]),
+    names("ro", "Română", &["ron", "rum", "moldovan", "romana"]),
+    names("ru", "Русский", &["rus", "russkiy"]),
+    names("sk", "Slovenčina", &["slk", "slo", "slovencina"]),
+    names("sl", "Slovenščina", &["slv", "slovene", "slovenscina"]),
+    names("sq", "Shqip", &["sqi", "alb"]),
+    names("sr", "Српски", &["srp", "srpski"]),
+    names("sv", "Svenska", &["swe"]),
+    names("sw", "Kiswahili", &["swa", "swahili"]),
+    names("ta", "தமிழ்", &["tam"]),
+    names("te", "తెలుగు", &["tel"]),
+    names("th", "ไทย", &["tha", "thai"]),
+    names("tr", "Türkçe", &["tur", "turkce"]),
+    names("uk", "Українська", &["ukr", "ukrainska"]),
+    names("ur", "اردو", &["urd"]),
+    names("vi", "Tiếng Việt", &["vie", "tieng viet"]),
+    names(
+        "zh",
+        "中文",
+        &["zho", "chi", "mandarin", "zhongwen", "putonghua"],
+    ),
+];
+
+fn lookup(code: &str) -> Option<&'static LanguageNames> {
+    LANGUAGE_NAMES
+        .iter()
+        .find(|names| names.code == code)
+        .or_else(|| {
+            // Script or region variants such as `zh_hant` share the base entry.
+            let base = code.split(['_', '-']).next()?;
+            LANGUAGE_NAMES.iter().find(|names| names.code == base)
+        })
+}
+
+/// The language's name in the language itself, if known.
+pub fn endonym(code: &str) -> Option<&'static str> {
+    lookup(code).map(|names| names.endonym)
+}
+
+/// Matches `query` (already lowercase) against the code, the English name,
+/// the endonym and known aliases.
+pub fn matches_query(code: &str, english_name: &str, query: &str) -> bool {
+    if query.is_empty() {
+        return true;
+    }
+    if code.eq_ignore_ascii_case(query) || english_name.to_lowercase().contains(query) {
+        return true;
+    }
+    lookup(code).is_some_and(|names| {
+        fold(names.endonym).contains(&fold(query))
+            || names.aliases.iter().any(|alias| alias.contains(query))
+    })
+}
+
+/// Lowercases and drops the most common Latin diacritics so "francais"
+/// finds "Français".
+fn fold(text: &str) -> String {
+    text.to_lowercase()
+        .chars()
+        .map(|ch| match ch {
+            'à' | 'á' | 'â' | 'ã' | 'ä' | 'å' | 'ă' => 'a',
+            'ç' | 'č' | 'ć' => 'c',
+            'è' | 'é' | 'ê' | 'ë' | 'ə' => 'e',
+            'ì' | 'í' | 'î' | 'ï' => 'i',
+            'ñ' | 'ň' => 'n',
+            'ò' | 'ó' | 'ô' | 'õ' | 'ö' | 'ø' => 'o',
+            'ù' | 'ú' | 'û' | 'ü' | 'ů' => 'u',
+            'š' | 'ș' | 'ş' => 's',
+            'ž' => 'z',
+            'ř' => 'r',
+            'ț' => 't',
+            'ý' => 'y',
+            other => other,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_languages_by_code_endonym_and_alias() {
+        assert!(matches_query("de", "German", "de"));
+        assert!(matches_query("de", "German", "deutsch"));
+        assert!(matches_query("fr", "French", "francais"));
+        assert!(matches_query("el", "Greek", "ελλ"));
+        assert!(matches_query("fa", "Persian", "farsi"));
+        assert!(matches_query("zh_hant", "Chinese (Traditional)", "中文"));
+        assert!(!matches_query("de", "German", "dutch"));
+    }
+}
@@ -0,0 +1,50 @@
+use serde::{Deserialize, Serialize};
+
+pub const MAX_RECENT_PAIRS: usize = 6;
+
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguagePair {
+    pub from: String,
+    pub to: String,
+}
+
+impl LanguagePair {
+    pub fn new(from: &str, to: &str) -> Self {
+        Self {
+            from: from.to_string(),
+            to: to.to_string(),
+        }
+    }
+}
+
+/// Moves `pair` to the front of `recent`; returns false when it already was there.
+pub fn remember_pair(recent: &mut Vec<LanguagePair>, pair: LanguagePair) -> bool {
+    if recent.first() == Some(&pair) {
+        return false;
+    }
+    recent.retain(|existing| *existing != pair);
+    recent.insert(0, pair);
+    recent.truncate(MAX_RECENT_PAIRS);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn remember_pair_moves_to_front_and_caps_length() {
+        let mut recent = (0..MAX_RECENT_PAIRS)
+            .map(|index| LanguagePair::new("en", &index.to_string()))
+            .collect::<Vec<_>>();
+
+        assert!(remember_pair(&mut recent, LanguagePair::new("en", "3")));
+        assert_eq!(recent[0], LanguagePair::new("en", "3"));
+        assert_eq!(recent.len(), MAX_RECENT_PAIRS);
+        assert!(!remember_pair(&mut recent, LanguagePair::new("en", "3")));
+
+        assert!(remember_pair(&mut recent, LanguagePair::new("de", "fr")));
+        assert_eq!(recent.len(), MAX_RECENT_PAIRS);
+        assert!(!recent.contains(&LanguagePair::new("en", "5")));
+    }
+}
@@ -1,14 +1,31 @@
 #![recursion_limit = "256"]
 
+mod batch;
 mod catalog_state;
+mod cli;
 mod data;
+mod dbus_service;
+mod detection;
 mod download;
 mod eventloop;
+mod glossary;
+mod http_api;
+mod i18n;
+mod image_export;
 mod image_ocr;
+mod image_preprocess;
+mod language_names;
+mod language_pairs;
 mod model;
+#[cfg(feature = "pdf")]
+mod pdf_render;
+mod placeholders;
 mod pulse;
+mod reading_order;
 mod rendered_image_item;
 mod settings;
+mod settings_watcher;
+mod translation;
 mod tts;
 mod ui;
 
@@ -21,8 +38,11 @@ use std::sync::mpsc;
 use translator::TranslatorSession;
 
 use crate::catalog_state::{bundled_catalog, languages_from_overview};
-use crate::model::FeatureKind;
+use crate::image_preprocess::Preprocess;
+use crate::model::{FeatureKind, OcrBackgroundMode, OcrReadingOrder};
+use crate::reading_order::Rect;
 use crate::settings::load_settings;
+use crate::translation::{TranslationOptions, TranslationSettings, TranslationTarget};
 use crate::ui::{AppBridge, create_ui_callbacks};
 
 const APP_NAME: &str = "dev.davidv.translator";
@@ -44,18 +64,65 @@ enum IoEvent {
         feature: FeatureKind,
     },
     SetAppPaths(AppPaths),
+    SetBatchWorkers(usize),
+    SetApiServer {
+        enabled: bool,
+        port: u16,
+    },
+    SetTranslationSettings(TranslationSettings),
+    /// With `auto_source`, `from` is only the fallback when detection is unsure.
     TranslationRequest {
         text: String,
         from: String,
         to: String,
+        auto_source: bool,
+        options: TranslationOptions,
+    },
+    MultiTranslationRequest {
+        text: String,
+        from: String,
+        auto_source: bool,
+        targets: Vec<TranslationTarget>,
     },
     ImageTranslationRequest {
         image_path: String,
+        /// Bumped for each document and each change that re-reads all its
+        /// pages; results for an older generation are dropped.
+        generation: u64,
+        /// Page of a multi-page document; 0 for a single image.
+        page: usize,
+        /// Show the result; otherwise only the page's text is reported, and
+        /// the page is read on a background thread.
+        show: bool,
+        /// Reuse the page's earlier result from this generation, if any. Set
+        /// only when the page is read without corrections or regions.
+        reuse_page: bool,
         from: String,
         to: String,
         min_confidence: u32,
         max_image_size: u32,
-        background_mode: String,
+        background_mode: OcrBackgroundMode,
+        /// Resolved for the source language; never `Automatic`.
+        reading_order: OcrReadingOrder,
+        /// Extract the text without translating it.
+        ocr_only: bool,
+        /// Areas of the image to read, in processed-image pixels; empty reads
+        /// the whole image.
+        regions: Vec<Rect>,
+        corrections: Preprocess,
+        options: TranslationOptions,
+    },
+    /// Splits a PDF or TIFF into page images, which are then read like photos.
+    DocumentPagesRequest {
+        document_path: String,
+    },
+    /// Re-translates one image block after its recognized text was corrected.
+    ImageBlockTranslationRequest {
+        index: usize,
+        text: String,
+        from: String,
+        to: String,
+        options: TranslationOptions,
     },
     RefreshTtsVoices {
         language_code: String,
@@ -71,6 +138,19 @@ enum IoEvent {
         voice_name: String,
     },
     StopTts,
+    ServiceTranslate {
+        text: String,
+        from: String,
+        to: String,
+        reply: mpsc::Sender<Result<String, String>>,
+    },
+    ServiceDetectLanguage {
+        text: String,
+        reply: mpsc::Sender<String>,
+    },
+    ServiceListLanguages {
+        reply: mpsc::Sender<Vec<(String, String)>>,
+    },
     Shutdown,
 }
 
@@ -92,6 +172,19 @@ fn get_app_paths() -> AppPaths {
 }
 
 fn main() -> Result<(), Box<dyn Error>> {
+    i18n::init();
+    let mut launch_request = match cli::parse_args(std::env::args().skip(1)) {
+        Ok(request) => request,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(2);
+        }
+    };
+    launch_request.normalize_file();
+    if dbus_service::forward_to_running_instance(&launch_request) {
+        return Ok(());
+    }
+
     configure_onnxruntime_dylib_path()?;
     qmetaobject::log::init_qt_to_rust();
     qml_register_type::<rendered_image_item::RenderedImageItem>(
@@ -112,6 +205,11 @@ fn main() -> Result<(), Box<dyn Error>> {
     let main_qml = find_main_qml()?;
     let asset_dir = find_asset_dir(&main_qml)?;
     let settings = load_settings(&app_paths.config);
+    let settings_batch_workers = settings.batch_workers as usize;
+    let api_server_event = IoEvent::SetApiServer {
+        enabled: settings.api_server_enabled,
+        port: settings.api_server_port,
+    };
     let mut engine = QmlEngine::new();
     let app = QObjectBox::new(AppBridge::new(
         initial_languages,
@@ -126,12 +224,32 @@ fn main() -> Result<(), Box<dyn Error>> {
     engine.set_object_property("app".into(), app.pinned());
 
     let ui_callbacks = create_ui_callbacks(QPointer::from(app.pinned().borrow()));
+    let _dbus_connection = match dbus_service::start_dbus_service(bus_tx.clone(), &ui_callbacks) {
+        Ok(connection) => Some(connection),
+        Err(err) => {
+            eprintln!("{err}");
+            None
+        }
+    };
+    settings_watcher::spawn_settings_watcher(
+        app_paths.config.clone(),
+        Arc::clone(&ui_callbacks.apply_external_settings),
+    );
     let session_for_loop = Arc::clone(&session);
     let jh = std::thread::spawn(move || {
         eventloop::run_eventloop(bus_rx, ui_callbacks, session_for_loop)
     });
 
+    bus_tx
+        .send(IoEvent::SetBatchWorkers(settings_batch_workers))
+        .unwrap();
     bus_tx.send(IoEvent::SetAppPaths(app_paths)).unwrap();
+    bus_tx.send(api_server_event).unwrap();
+    if !launch_request.is_empty() {
+        app.pinned()
+            .borrow_mut()
+            .apply_launch_request(launch_request);
+    }
     engine.load_file(main_qml.into());
     engine.exec();
 
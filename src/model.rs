@@ -1,3 +1,5 @@
+use serde::{Deserialize, Serialize};
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Direction {
     FromOnly,
@@ -22,6 +24,109 @@ impl From<FeatureKind> for translator::Feature {
     }
 }
 
+/// How OCR separates text from its background.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrBackgroundMode {
+    #[default]
+    AutoDetect,
+    LightBackground,
+    DarkBackground,
+}
+
+impl OcrBackgroundMode {
+    /// English label, as listed in the settings combo box.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::AutoDetect => "Auto-detect Colors",
+            Self::LightBackground => "Light Background",
+            Self::DarkBackground => "Dark Background",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        [
+            Self::AutoDetect,
+            Self::LightBackground,
+            Self::DarkBackground,
+        ]
+        .into_iter()
+        .find(|mode| mode.label() == label)
+    }
+}
+
+impl From<OcrBackgroundMode> for translator::BackgroundMode {
+    fn from(value: OcrBackgroundMode) -> Self {
+        match value {
+            OcrBackgroundMode::AutoDetect => translator::BackgroundMode::AutoDetect,
+            OcrBackgroundMode::LightBackground => translator::BackgroundMode::BlackOnWhite,
+            OcrBackgroundMode::DarkBackground => translator::BackgroundMode::WhiteOnBlack,
+        }
+    }
+}
+
+impl From<OcrReadingOrder> for translator::ReadingOrder {
+    /// `Automatic` is resolved with `for_script` before it reaches the engine.
+    fn from(value: OcrReadingOrder) -> Self {
+        match value {
+            OcrReadingOrder::Automatic | OcrReadingOrder::LeftToRight => {
+                translator::ReadingOrder::LeftToRight
+            }
+            OcrReadingOrder::RightToLeft => translator::ReadingOrder::RightToLeft,
+            OcrReadingOrder::Vertical => translator::ReadingOrder::TopToBottom,
+        }
+    }
+}
+
+/// Order in which OCR blocks, and the lines inside them, are read.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum OcrReadingOrder {
+    /// Chosen from the source language's script.
+    #[default]
+    Automatic,
+    LeftToRight,
+    RightToLeft,
+    /// Top-to-bottom columns, read from the right. Blocks laid out
+    /// horizontally are still read left to right. Only set by hand: most
+    /// Chinese and Japanese text in photos and screenshots is horizontal.
+    Vertical,
+}
+
+impl OcrReadingOrder {
+    pub const ALL: [Self; 4] = [
+        Self::Automatic,
+        Self::LeftToRight,
+        Self::RightToLeft,
+        Self::Vertical,
+    ];
+
+    /// English label, as listed in the settings combo box.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Automatic => "Automatic",
+            Self::LeftToRight => "Left to right",
+            Self::RightToLeft => "Right to left",
+            Self::Vertical => "Vertical",
+        }
+    }
+
+    pub fn from_label(label: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|order| order.label() == label)
+    }
+
+    /// Resolves `Automatic` from an ISO 15924 script code such as `Arab`.
+    pub fn for_script(self, script: &str) -> Self {
+        if self != Self::Automatic {
+            return self;
+        }
+        match script {
+            "Arab" | "Hebr" | "Syrc" | "Thaa" | "Nkoo" => Self::RightToLeft,
+            _ => Self::LeftToRight,
+        }
+    }
+}
+
 #[derive(Clone, Debug, Default)]
 pub struct TtsVoicePackOption {
     pub pack_id: String,
@@ -41,7 +146,9 @@ pub struct TtsVoicePickerRegion {
 #[derive(Clone, Debug, Default)]
 pub struct Language {
     pub code: String,
+    /// Name shown in the UI; the endonym when native names are enabled.
     pub name: String,
+    pub english_name: String,
     pub script: String,
     pub dictionary_code: String,
     pub direction: Direction,
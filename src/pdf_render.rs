@@ -0,0 +1,56 @@
+use std::path::Path;
+
+use cpp::cpp;
+
+cpp! {{
+    #include <poppler-qt5.h>
+    #include <QtGui/QImage>
+    #include <memory>
+}}
+
+/// Renders every page of `pdf` at `dpi` to `<prefix>-NNNN.png`, numbered from
+/// 1 and zero-padded so the names sort in page order. Returns the page count.
+pub fn render_pages(pdf: &Path, prefix: &Path, dpi: u32) -> Result<usize, String> {
+    let pdf_path = pdf.to_string_lossy();
+    let prefix_path = prefix.to_string_lossy();
+    let pdf_ptr = pdf_path.as_ptr();
+    let pdf_len = pdf_path.len();
+    let prefix_ptr = prefix_path.as_ptr();
+    let prefix_len = prefix_path.len();
+    let dpi = dpi as f64;
+    let rendered = cpp!(unsafe [
+        pdf_ptr as "const char *",
+        pdf_len as "size_t",
+        prefix_ptr as "const char *",
+        prefix_len as "size_t",
+        dpi as "double"
+    ] -> i32 as "int" {
+        const QString pdf = QString::fromUtf8(pdf_ptr, static_cast<int>(pdf_len));
+        const QString prefix = QString::fromUtf8(prefix_ptr, static_cast<int>(prefix_len));
+        std::unique_ptr<Poppler::Document> document(Poppler::Document::load(pdf));
+        if (!document || document->isLocked()) {
+            return -1;
+        }
+        document->setRenderHint(Poppler::Document::Antialiasing);
+        document->setRenderHint(Poppler::Document::TextAntialiasing);
+        const int count = document->numPages();
+        for (int index = 0; index < count; ++index) {
+            std::unique_ptr<Poppler::Page> page(document->page(index));
+            if (!page) {
+                return -2;
+            }
+            const QImage image = page->renderToImage(dpi, dpi);
+            const QString path =
+                QString("%1-%2.png").arg(prefix).arg(index + 1, 4, 10, QChar('0'));
+            if (image.isNull() || !image.save(path, "PNG")) {
+                return -2;
+            }
+        }
+        return count;
+    });
+    match rendered {
+        -1 => Err(format!("Couldn't open PDF {}", pdf.display())),
+        count if count < 0 => Err(format!("Couldn't render PDF {}", pdf.display())),
+        count => Ok(count as usize),
+    }
+}
@@ -0,0 +1,502 @@
+use serde::{Deserialize, Serialize};
+
+use crate::glossary::GlossaryEntry;
+
+const UNITS: &[&str] = &[
+    "km/h", "m/s", "kWh", "MHz", "GHz", "kHz", "mph", "min", "ms", "°C", "°F", "km", "cm", "mm",
+    "kg", "mg", "ml", "mL", "KB", "MB", "GB", "TB", "kB", "px", "pt", "kW", "Hz", "ft", "lbs",
+    "lb", "oz", "m", "g", "l", "L", "h", "s", "W", "V", "%",
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PlaceholderProtection {
+    #[serde(default = "default_enabled")]
+    pub braces: bool,
+    #[serde(default = "default_enabled")]
+    pub printf: bool,
+    #[serde(default = "default_enabled")]
+    pub urls: bool,
+    #[serde(default = "default_enabled")]
+    pub emails: bool,
+    #[serde(default = "default_enabled")]
+    pub units: bool,
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Default for PlaceholderProtection {
+    fn default() -> Self {
+        Self {
+            braces: true,
+            printf: true,
+            urls: true,
+            emails: true,
+            units: true,
+        }
+    }
+}
+
+/// Text with protected tokens replaced by `{n}` markers, which the models
+/// reliably copy through untouched. Each token holds the text restored in
+/// its place, which for glossary terms is the required target term.
+pub struct MaskedText {
+    pub text: String,
+    tokens: Vec<String>,
+}
+
+impl MaskedText {
+    pub fn restore(&self, translated: &str) -> String {
+        if self.tokens.is_empty() {
+            return translated.to_string();
+        }
+
+        let chars = translated.char_indices().collect::<Vec<_>>();
+        let mut restored = String::with_capacity(translated.len());
+        let mut seen = vec![false; self.tokens.len()];
+        let mut index = 0usize;
+
+        while index < chars.len() {
+            if let Some((token_index, end)) = parse_marker(&chars, index)
+                && token_index < self.tokens.len()
+            {
+                restored.push_str(&self.tokens[token_index]);
+                seen[token_index] = true;
+                index = end;
+                continue;
+            }
+            restored.push(chars[index].1);
+            index += 1;
+        }
+
+        let missing = seen.iter().filter(|seen| !**seen).count();
+        if missing > 0 {
+            eprintln!("placeholders: {missing} protected token(s) dropped by the model");
+        }
+        restored
+    }
+}
+
+pub fn mask(
+    text: &str,
+    protection: &PlaceholderProtection,
+    glossary: &[GlossaryEntry],
+) -> MaskedText {
+    let chars = text.char_indices().collect::<Vec<_>>();
+    let mut masked = String::with_capacity(text.len());
+    let mut tokens = Vec::new();
+    let mut index = 0usize;
+
+    while index < chars.len() {
+        let at_boundary = index == 0 || !is_word_char(chars[index - 1].1);
+        if at_boundary && let Some((entry, end)) = match_glossary_term(&chars, index, glossary) {
+            let start_byte = chars[index].0;
+            let end_byte = chars.get(end).map(|(byte, _)| *byte).unwrap_or(text.len());
+            masked.push_str(&format!("{{{}}}", tokens.len()));
+            tokens.push(
+                entry
+                    .target
+                    .clone()
+                    .unwrap_or_else(|| text[start_byte..end_byte].to_string()),
+            );
+            index = end;
+            continue;
+        }
+
+        let matched = parse_marker(&chars, index)
+            .map(|(_, end)| end)
+            .or_else(|| {
+                (protection.urls && at_boundary)
+                    .then(|| match_url(&chars, index))
+                    .flatten()
+            })
+            .or_else(|| {
+                (protection.emails && at_boundary)
+                    .then(|| match_email(&chars, index))
+                    .flatten()
+            })
+            .or_else(|| {
+                protection
+                    .braces
+                    .then(|| match_braces(&chars, index))
+                    .flatten()
+            })
+            .or_else(|| {
+                protection
+                    .printf
+                    .then(|| match_printf(&chars, index))
+                    .flatten()
+            })
+            .or_else(|| {
+                (protection.units && at_boundary)
+                    .then(|| match_number_with_unit(&chars, index))
+                    .flatten()
+            });
+
+        match matched {
+            Some(end) => {
+                let start_byte = chars[index].0;
+                let end_byte = chars.get(end).map(|(byte, _)| *byte).unwrap_or(text.len());
+                masked.push_str(&format!("{{{}}}", tokens.len()));
+                tokens.push(text[start_byte..end_byte].to_string());
+                index = end;
+            }
+            None => {
+                masked.push(chars[index].1);
+                index += 1;
+            }
+        }
+    }
+
+    MaskedText {
+        text: masked,
+        tokens,
+    }
+}
+
+/// Whether `text` contains any glossary term as a whole word.
+pub fn contains_glossary_term(text: &str, glossary: &[GlossaryEntry]) -> bool {
+    if glossary.is_empty() {
+        return false;
+    }
+    let chars = text.char_indices().collect::<Vec<_>>();
+    (0..chars.len()).any(|index| {
+        (index == 0 || !is_word_char(chars[index - 1].1))
+            && match_glossary_term(&chars, index, glossary).is_some()
+    })
+}
+
+/// Finds the longest glossary term starting at `start`, ignoring case.
+fn match_glossary_term<'a>(
+    chars: &[(usize, char)],
+    start: usize,
+    glossary: &'a [GlossaryEntry],
+) -> Option<(&'a GlossaryEntry, usize)> {
+    glossary
+        .iter()
+        .filter_map(|entry| {
+            let mut index = start;
+            for expected in entry.source.chars() {
+                let (_, ch) = chars.get(index)?;
+                if !ch.to_lowercase().eq(expected.to_lowercase()) {
+                    return None;
+                }
+                index += 1;
+            }
+            let at_boundary = chars.get(index).is_none_or(|(_, ch)| !is_word_char(*ch));
+            (index > start && at_boundary).then_some((entry, index))
+        })
+        .max_by_key(|(_, end)| *end)
+}
+
+fn is_word_char(ch: char) -> bool {
+    ch.is_alphanumeric() || ch == '_'
+}
+
+/// Parses `{n}`, tolerating the spaces some models insert around the digits.
+fn parse_marker(chars: &[(usize, char)], start: usize) -> Option<(usize, usize)> {
+    if chars.get(start)?.1 != '{' {
+        return None;
+    }
+    let mut index = start + 1;
+    while chars.get(index).is_some_and(|(_, ch)| *ch == ' ') {
+        index += 1;
+    }
+    let digits_start = index;
+    let mut value = 0usize;
+    while let Some(digit) = chars.get(index).and_then(|(_, ch)| ch.to_digit(10)) {
+        value = value.checked_mul(10)?.checked_add(digit as usize)?;
+        index += 1;
+    }
+    if index == digits_start {
+        return None;
+    }
+    while chars.get(index).is_some_and(|(_, ch)| *ch == ' ') {
+        index += 1;
+    }
+    (chars.get(index)?.1 == '}').then_some((value, index + 1))
+}
+
+fn starts_with(chars: &[(usize, char)], start: usize, prefix: &str) -> bool {
+    let mut index = start;
+    for expected in prefix.chars() {
+        match chars.get(index) {
+            Some((_, ch)) if ch.eq_ignore_ascii_case(&expected) => index += 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+fn match_url(chars: &[(usize, char)], start: usize) -> Option<usize> {
+    let prefix_len = ["https://", "http://", "ftp://", "www."]
+        .iter()
+        .find(|prefix| starts_with(chars, start, prefix))?
+        .len();
+
+    let mut end = start + prefix_len;
+    while chars
+        .get(end)
+        .is_some_and(|(_, ch)| !ch.is_whitespace() && !matches!(ch, '<' | '>' | '"'))
+    {
+        end += 1;
+    }
+    while end > start + prefix_len
+        && matches!(
+            chars[end - 1].1,
+            '.' | ',' | ';' | ':' | '!' | '?' | ')' | ']' | '}' | '\''
+        )
+    {
+        end -= 1;
+    }
+    (end > start + prefix_len).then_some(end)
+}
+
+fn match_email(chars: &[(usize, char)], start: usize) -> Option<usize> {
+    let is_local =
+        |ch: char| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '_' | '%' | '+' | '-');
+    let is_domain = |ch: char| ch.is_ascii_alphanumeric() || matches!(ch, '.' | '-');
+
+    let mut index = start;
+    while chars.get(index).is_some_and(|(_, ch)| is_local(*ch)) {
+        index += 1;
+    }
+    if index == start || chars.get(index)?.1 != '@' {
+        return None;
+    }
+
+    let domain_start = index + 1;
+    let mut end = domain_start;
+    while chars.get(end).is_some_and(|(_, ch)| is_domain(*ch)) {
+        end += 1;
+    }
+    while end > domain_start && matches!(chars[end - 1].1, '.' | '-') {
+        end -= 1;
+    }
+    let has_dot = chars[domain_start..end].iter().any(|(_, ch)| *ch == '.');
+    (end > domain_start && has_dot).then_some(end)
+}
+
+fn match_braces(chars: &[(usize, char)], start: usize) -> Option<usize> {
+    const MAX_NAME_LEN: usize = 64;
+
+    let mut index = start;
+    if chars.get(index)?.1 == '$' {
+        index += 1;
+    }
+    let mut depth = 0usize;
+    while chars.get(index).is_some_and(|(_, ch)| *ch == '{') && depth < 2 {
+        depth += 1;
+        index += 1;
+    }
+    if depth == 0 {
+        return None;
+    }
+
+    let name_start = index;
+    while chars
+        .get(index)
+        .is_some_and(|(_, ch)| ch.is_alphanumeric() || matches!(ch, '_' | '.' | ':' | '-' | ' '))
+        && index - name_start < MAX_NAME_LEN
+    {
+        index += 1;
+    }
+    let name = &chars[name_start..index];
+    if name.iter().all(|(_, ch)| *ch == ' ') {
+        return None;
+    }
+
+    for _ in 0..depth {
+        if chars.get(index)?.1 != '}' {
+            return None;
+        }
+        index += 1;
+    }
+    Some(index)
+}
+
+fn match_printf(chars: &[(usize, char)], start: usize) -> Option<usize> {
+    if chars.get(start)?.1 != '%' {
+        return None;
+    }
+    let mut index = start + 1;
+    let ch_at = |index: usize| chars.get(index).map(|(_, ch)| *ch);
+
+    if ch_at(index) == Some('%') {
+        return Some(index + 1);
+    }
+    if ch_at(index) == Some('(') {
+        while ch_at(index).is_some_and(|ch| ch != ')' && !ch.is_whitespace()) {
+            index += 1;
+        }
+        if ch_at(index) != Some(')') {
+            return None;
+        }
+        index += 1;
+    } else {
+        let digits_start = index;
+        while ch_at(index).is_some_and(|ch| ch.is_ascii_digit()) {
+            index += 1;
+        }
+        if ch_at(index) == Some('$') {
+            index += 1;
+        } else {
+            index = digits_start;
+        }
+    }
+
+    while ch_at(index).is_some_and(|ch| matches!(ch, '-' | '+' | ' ' | '0' | '#')) {
+        index += 1;
+    }
+    while ch_at(index).is_some_and(|ch| ch.is_ascii_digit() || ch == '*') {
+        index += 1;
+    }
+    if ch_at(index) == Some('.') {
+        index += 1;
+        while ch_at(index).is_some_and(|ch| ch.is_ascii_digit() || ch == '*') {
+            index += 1;
+        }
+    }
+    while ch_at(index).is_some_and(|ch| matches!(ch, 'h' | 'l' | 'L' | 'z' | 'j' | 't')) {
+        index += 1;
+    }
+
+    let conversion = ch_at(index)?;
+    if !"diouxXeEfFgGaAcspn@".contains(conversion) {
+        return None;
+    }
+    let end = index + 1;
+    // "%s" inside a word such as "50%sale" is not a placeholder.
+    if ch_at(end).is_some_and(is_word_char) && conversion != '@' {
+        return None;
+    }
+    Some(end)
+}
+
+fn match_number_with_unit(chars: &[(usize, char)], start: usize) -> Option<usize> {
+    let ch_at = |index: usize| chars.get(index).map(|(_, ch)| *ch);
+
+    let mut index = start;
+    if matches!(ch_at(index), Some('-' | '+')) {
+        index += 1;
+    }
+    let digits_start = index;
+    while let Some(ch) = ch_at(index) {
+        let decimal_separator = matches!(ch, '.' | ',')
+            && index > digits_start
+            && ch_at(index + 1).is_some_and(|next| next.is_ascii_digit());
+        if !ch.is_ascii_digit() && !decimal_separator {
+            break;
+        }
+        index += 1;
+    }
+    if index == digits_start {
+        return None;
+    }
+
+    let unit_start = if matches!(ch_at(index), Some(' ' | '\u{a0}')) {
+        index + 1
+    } else {
+        index
+    };
+    UNITS.iter().find_map(|unit| {
+        if !starts_with_exact(chars, unit_start, unit) {
+            return None;
+        }
+        let end = unit_start + unit.chars().count();
+        let at_boundary = ch_at(end).is_none_or(|ch| !is_word_char(ch));
+        at_boundary.then_some(end)
+    })
+}
+
+fn starts_with_exact(chars: &[(usize, char)], start: usize, prefix: &str) -> bool {
+    let mut index = start;
+    for expected in prefix.chars() {
+        match chars.get(index) {
+            Some((_, ch)) if *ch == expected => index += 1,
+            _ => return false,
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn masked_tokens(text: &str) -> Vec<String> {
+        mask(text, &PlaceholderProtection::default(), &[]).tokens
+    }
+
+    #[test]
+    fn masks_and_restores_every_pattern() {
+        let text = "Hi {user}, you have %d new messages from bob@example.com at https://example.com/inbox. Only 5 km left!";
+        let masked = mask(text, &PlaceholderProtection::default(), &[]);
+        assert_eq!(
+            masked.text,
+            "Hi {0}, you have {1} new messages from {2} at {3}. Only {4} left!"
+        );
+        assert_eq!(masked.restore(&masked.text), text);
+    }
+
+    #[test]
+    fn restore_tolerates_spaced_markers() {
+        let masked = mask("Hello {name}", &PlaceholderProtection::default(), &[]);
+        assert_eq!(masked.restore("Hallo { 0 }"), "Hallo {name}");
+    }
+
+    #[test]
+    fn disabled_patterns_are_left_alone() {
+        let protection = PlaceholderProtection {
+            urls: false,
+            units: false,
+            ..Default::default()
+        };
+        let masked = mask("see www.example.com, 12 kg", &protection, &[]);
+        assert!(masked.tokens.is_empty());
+        assert_eq!(masked.text, "see www.example.com, 12 kg");
+    }
+
+    #[test]
+    fn existing_marker_lookalikes_are_always_protected() {
+        let protection = PlaceholderProtection {
+            braces: false,
+            ..Default::default()
+        };
+        let masked = mask("footnote {3}", &protection, &[]);
+        assert_eq!(masked.text, "footnote {0}");
+        assert_eq!(masked.restore("Fußnote {0}"), "Fußnote {3}");
+    }
+
+    #[test]
+    fn glossary_terms_restore_to_their_target() {
+        let glossary = [
+            GlossaryEntry {
+                source: "Acme Cloud".to_string(),
+                target: None,
+            },
+            GlossaryEntry {
+                source: "Vertrag".to_string(),
+                target: Some("agreement".to_string()),
+            },
+        ];
+        let masked = mask(
+            "Der vertrag für ACME Cloud",
+            &PlaceholderProtection::default(),
+            &glossary,
+        );
+        assert_eq!(masked.text, "Der {0} für {1}");
+        assert_eq!(
+            masked.restore("The {0} for {1}"),
+            "The agreement for ACME Cloud"
+        );
+    }
+
+    #[test]
+    fn ignores_plain_numbers_and_words() {
+        assert!(masked_tokens("I have 3 cats and 50%sale").is_empty());
+        assert_eq!(masked_tokens("grew 12,5% and %1$s"), ["12,5%", "%1$s"]);
+        assert_eq!(masked_tokens("{{count}} items"), ["{{count}}"]);
+    }
+}
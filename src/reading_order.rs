@@ -0,0 +1,66 @@
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Rect {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Text is vertical when most of its lines are taller than they are wide.
+pub fn is_vertical_text(lines: &[Rect]) -> bool {
+    let tall = lines.iter().filter(|line| line.height > line.width).count();
+    !lines.is_empty() && tall * 2 > lines.len()
+}
+
+/// Horizontal rows covering a vertical text block, sized like its columns, so
+/// horizontal translations can be laid out over it.
+pub fn horizontal_rows(block: Rect, columns: &[Rect]) -> Vec<Rect> {
+    let mut widths = columns
+        .iter()
+        .map(|column| column.width)
+        .collect::<Vec<_>>();
+    widths.sort_unstable();
+    let row_height = widths
+        .get(widths.len() / 2)
+        .copied()
+        .unwrap_or(block.height)
+        .clamp(1, block.height.max(1));
+    let count = (block.height / row_height).max(1);
+    (0..count)
+        .map(|row| Rect {
+            x: block.x,
+            y: block.y + row * row_height,
+            width: block.width,
+            height: row_height,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: u32, y: u32, width: u32, height: u32) -> Rect {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn detects_vertical_text_and_lays_rows_over_it() {
+        let columns = vec![
+            rect(10, 10, 20, 100),
+            rect(50, 10, 20, 60),
+            rect(50, 80, 20, 40),
+        ];
+        assert!(is_vertical_text(&columns));
+        assert!(!is_vertical_text(&[rect(10, 10, 80, 20)]));
+
+        let rows = horizontal_rows(rect(10, 10, 60, 100), &columns);
+        assert_eq!(rows.len(), 5);
+        assert_eq!(rows[1], rect(10, 30, 60, 20));
+    }
+}
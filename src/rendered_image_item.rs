@@ -7,6 +7,8 @@ cpp! {{
     #include <QtQuick/QQuickWindow>
     #include <QtGui/QScreen>
     #include <QtGui/QPixmap>
+    #include <QtGui/QPainter>
+    #include <QtGui/QFontMetricsF>
     #include <cstring>
 }}
 
@@ -95,3 +97,58 @@ pub fn save_window_screenshot(path: &str) -> bool {
         return shot.save(path);
     })
 }
+
+/// Advance width and height of `text` in the application font at `pixel_size`.
+pub fn text_size(text: &str, pixel_size: u32) -> QSizeF {
+    let text = QString::from(text);
+    let pixel_size = pixel_size as i32;
+    cpp!(unsafe [text as "QString", pixel_size as "int"] -> QSizeF as "QSizeF" {
+        QFont font = QGuiApplication::font();
+        font.setPixelSize(pixel_size);
+        const QFontMetricsF metrics(font);
+        return QSizeF(metrics.horizontalAdvance(text), metrics.boundingRect(text).height());
+    })
+}
+
+/// Paints one line of overlay text into `rect` of `image`, over a fill of
+/// `background` so no original text shows through.
+pub fn paint_text_line(
+    image: &mut QImage,
+    rect: QRectF,
+    text: &str,
+    pixel_size: u32,
+    background: &QString,
+    foreground: &QString,
+    right_to_left: bool,
+) {
+    let text = QString::from(text);
+    let pixel_size = pixel_size as i32;
+    cpp!(unsafe [
+        image as "QImage*",
+        rect as "QRectF",
+        text as "QString",
+        pixel_size as "int",
+        background as "const QString*",
+        foreground as "const QString*",
+        right_to_left as "bool"
+    ] {
+        QPainter painter(image);
+        painter.setRenderHint(QPainter::TextAntialiasing);
+        painter.fillRect(rect, QColor(*background));
+        QFont font = QGuiApplication::font();
+        font.setPixelSize(pixel_size);
+        painter.setFont(font);
+        painter.setPen(QColor(*foreground));
+        painter.setClipRect(rect);
+        painter.setLayoutDirection(right_to_left ? Qt::RightToLeft : Qt::LeftToRight);
+        painter.drawText(rect, Qt::AlignTop | (right_to_left ? Qt::AlignRight : Qt::AlignLeft), text);
+    })
+}
+
+/// Writes `image` to `path`, in the format its extension names.
+pub fn save_image(image: &QImage, path: &str, quality: i32) -> bool {
+    let path = QString::from(path);
+    cpp!(unsafe [image as "const QImage*", path as "QString", quality as "int"] -> bool as "bool" {
+        return image->save(path, nullptr, quality);
+    })
+}
@@ -1,16 +1,40 @@
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use std::collections::BTreeMap;
 use std::fs;
+use std::io::Write;
 use std::path::Path;
+use std::sync::Mutex;
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+use crate::glossary::Glossary;
+use crate::language_pairs::LanguagePair;
+use crate::model::{OcrBackgroundMode, OcrReadingOrder};
+use crate::placeholders::PlaceholderProtection;
+
+/// Bumped whenever a stored field changes meaning; see `MIGRATIONS`.
+pub const SETTINGS_SCHEMA_VERSION: u32 = 1;
+
+pub const SETTINGS_FILE_NAME: &str = "settings.json";
+
+/// What `save_settings` last wrote, so our own writes can be told apart from
+/// external edits.
+static LAST_SAVED: Mutex<String> = Mutex::new(String::new());
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct Settings {
+    #[serde(default)]
+    pub schema_version: u32,
     #[serde(default = "default_lang_code")]
     pub default_from_code: String,
     #[serde(default = "default_lang_code")]
     pub default_to_code: String,
-    #[serde(default = "default_ocr_background_mode")]
-    pub ocr_background_mode: String,
+    #[serde(default)]
+    pub ocr_background_mode: OcrBackgroundMode,
+    #[serde(default)]
+    pub ocr_reading_order: OcrReadingOrder,
+    /// Straighten tilted photos and turn sideways text upright before OCR.
+    #[serde(default = "default_ocr_straighten")]
+    pub ocr_straighten: bool,
     #[serde(default = "default_ocr_min_confidence")]
     pub ocr_min_confidence: i32,
     #[serde(default = "default_ocr_max_image_size")]
@@ -22,6 +46,11 @@ pub struct Settings {
     #[serde(default)]
     pub disable_auto_detect: bool,
     #[serde(default)]
+    pub native_language_names: bool,
+    /// Translate from the detected language when it is reliable and installed.
+    #[serde(default)]
+    pub auto_switch_source: bool,
+    #[serde(default)]
     pub show_transliteration_output: bool,
     #[serde(default)]
     pub show_transliteration_input: bool,
@@ -29,13 +58,34 @@ pub struct Settings {
     pub tts_playback_speed: f32,
     #[serde(default)]
     pub tts_voice_overrides: BTreeMap<String, String>,
+    #[serde(default)]
+    pub placeholder_protection: PlaceholderProtection,
+    #[serde(default)]
+    pub direct_translation: bool,
+    #[serde(default)]
+    pub extra_target_codes: Vec<String>,
+    #[serde(default)]
+    pub recent_pairs: Vec<LanguagePair>,
+    #[serde(default)]
+    pub pinned_pairs: Vec<LanguagePair>,
+    /// Worker threads for paragraph-level batch translation; 0 means one per core.
+    #[serde(default)]
+    pub batch_workers: u32,
+    #[serde(default)]
+    pub api_server_enabled: bool,
+    #[serde(default = "default_api_server_port")]
+    pub api_server_port: u16,
+    #[serde(default)]
+    pub translate_clipboard_on_focus: bool,
+    #[serde(default)]
+    pub clipboard_use_selection: bool,
 }
 
 fn default_lang_code() -> String {
     "en".to_string()
 }
-fn default_ocr_background_mode() -> String {
-    "Auto-detect Colors".to_string()
+fn default_ocr_straighten() -> bool {
+    true
 }
 fn default_ocr_min_confidence() -> i32 {
     75
@@ -49,43 +99,332 @@ fn default_catalog_index_url() -> String {
 fn default_tts_playback_speed() -> f32 {
     1.0
 }
+fn default_api_server_port() -> u16 {
+    5000
+}
 
 impl Default for Settings {
     fn default() -> Self {
         Self {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             default_from_code: default_lang_code(),
             default_to_code: default_lang_code(),
-            ocr_background_mode: default_ocr_background_mode(),
+            ocr_background_mode: OcrBackgroundMode::default(),
+            ocr_reading_order: OcrReadingOrder::default(),
+            ocr_straighten: default_ocr_straighten(),
             ocr_min_confidence: default_ocr_min_confidence(),
             ocr_max_image_size: default_ocr_max_image_size(),
             catalog_index_url: default_catalog_index_url(),
             disable_ocr: false,
             disable_auto_detect: false,
+            native_language_names: false,
+            auto_switch_source: false,
             show_transliteration_output: false,
             show_transliteration_input: false,
             tts_playback_speed: default_tts_playback_speed(),
             tts_voice_overrides: BTreeMap::new(),
+            placeholder_protection: PlaceholderProtection::default(),
+            direct_translation: false,
+            extra_target_codes: Vec::new(),
+            recent_pairs: Vec::new(),
+            pinned_pairs: Vec::new(),
+            batch_workers: 0,
+            api_server_enabled: false,
+            api_server_port: default_api_server_port(),
+            translate_clipboard_on_focus: false,
+            clipboard_use_selection: false,
         }
     }
 }
 
+impl Settings {
+    /// Pulls out-of-range values back to something the UI can represent.
+    fn validate(&mut self) {
+        let defaults = Settings::default();
+        self.ocr_min_confidence = self.ocr_min_confidence.clamp(0, 100);
+        self.ocr_max_image_size = self.ocr_max_image_size.clamp(1500, 4000);
+        if !self.tts_playback_speed.is_finite() {
+            self.tts_playback_speed = defaults.tts_playback_speed;
+        }
+        self.tts_playback_speed = self.tts_playback_speed.clamp(0.5, 2.0);
+        if self.catalog_index_url.trim().is_empty() {
+            self.catalog_index_url = defaults.catalog_index_url;
+        }
+        if self.api_server_port < 1024 {
+            self.api_server_port = defaults.api_server_port;
+        }
+        if self.default_from_code.is_empty() {
+            self.default_from_code = defaults.default_from_code;
+        }
+        if self.default_to_code.is_empty() {
+            self.default_to_code = defaults.default_to_code;
+        }
+    }
+}
+
+/// `MIGRATIONS[n]` upgrades a version `n` file to version `n + 1`.
+const MIGRATIONS: &[fn(&mut Map<String, Value>)] = &[migrate_v0_background_mode_labels];
+
+/// Version 0 stored the OCR background mode as its English combo-box label.
+fn migrate_v0_background_mode_labels(settings: &mut Map<String, Value>) {
+    if let Some(Value::String(label)) = settings.get("ocr_background_mode") {
+        let mode = OcrBackgroundMode::from_label(label).unwrap_or_default();
+        settings.insert(
+            "ocr_background_mode".to_string(),
+            serde_json::to_value(mode).expect("background mode should serialize"),
+        );
+    }
+}
+
 pub fn load_settings(config_dir: &str) -> Settings {
-    let path = Path::new(config_dir).join("settings.json");
+    let path = Path::new(config_dir).join(SETTINGS_FILE_NAME);
     let contents = match fs::read_to_string(&path) {
         Ok(c) => c,
         Err(_) => return Settings::default(),
     };
     eprintln!("settings: loading from {}", path.display());
-    let settings: Settings = serde_json::from_str(&contents).unwrap_or_default();
+    let settings = parse_settings(&contents);
     eprintln!(
-        "settings: loaded from_code={} to_code={} ocr_bg={}",
+        "settings: loaded from_code={} to_code={} ocr_bg={:?}",
         settings.default_from_code, settings.default_to_code, settings.ocr_background_mode
     );
     settings
 }
 
+/// Re-reads the settings file after it changed on disk. Returns `None` when it
+/// is missing or still holds what this process wrote last.
+pub fn load_external_change(config_dir: &str) -> Option<Settings> {
+    let path = Path::new(config_dir).join(SETTINGS_FILE_NAME);
+    let contents = fs::read_to_string(&path).ok()?;
+    if *LAST_SAVED.lock().unwrap() == contents {
+        return None;
+    }
+    eprintln!("settings: {} changed on disk", path.display());
+    Some(parse_settings(&contents))
+}
+
+fn parse_settings(contents: &str) -> Settings {
+    match serde_json::from_str::<Value>(contents) {
+        Ok(Value::Object(stored)) => settings_from_object(stored),
+        Ok(_) | Err(_) => {
+            eprintln!("settings: file is not a JSON object, using defaults");
+            Settings::default()
+        }
+    }
+}
+
+/// Migrates and validates stored settings of any schema version.
+fn settings_from_object(mut stored: Map<String, Value>) -> Settings {
+    let version = stored
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as usize;
+    if version > SETTINGS_SCHEMA_VERSION as usize {
+        eprintln!(
+            "settings: schema version {version} is newer than this build, loading what we can"
+        );
+    }
+    for migration in MIGRATIONS.iter().skip(version) {
+        migration(&mut stored);
+    }
+    stored.insert(
+        "schema_version".to_string(),
+        Value::from(SETTINGS_SCHEMA_VERSION),
+    );
+
+    // Take the stored fields one by one so a single bad value only resets itself.
+    let Value::Object(mut merged) =
+        serde_json::to_value(Settings::default()).expect("settings should serialize")
+    else {
+        unreachable!("settings serialize to an object");
+    };
+    for (key, value) in stored {
+        let previous = merged.insert(key.clone(), value);
+        if serde_json::from_value::<Settings>(Value::Object(merged.clone())).is_err() {
+            eprintln!("settings: ignoring invalid value for '{key}'");
+            match previous {
+                Some(previous) => merged.insert(key, previous),
+                None => merged.remove(&key),
+            };
+        }
+    }
+
+    let mut settings =
+        serde_json::from_value::<Settings>(Value::Object(merged)).unwrap_or_default();
+    settings.validate();
+    settings
+}
+
+/// Writes to a temporary file first so a crash mid-write can't leave a
+/// truncated settings file behind.
 pub fn save_settings(config_dir: &str, settings: &Settings) {
-    let path = Path::new(config_dir).join("settings.json");
+    let path = Path::new(config_dir).join(SETTINGS_FILE_NAME);
+    let tmp_path = path.with_extension("json.tmp");
     let json = serde_json::to_string_pretty(settings).expect("settings should serialize");
-    let _ = fs::write(path, json);
+    *LAST_SAVED.lock().unwrap() = json.clone();
+    let result = fs::File::create(&tmp_path)
+        .and_then(|mut file| {
+            file.write_all(json.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|_| fs::rename(&tmp_path, &path));
+    if let Err(err) = result {
+        eprintln!("settings: failed to save {}: {err}", path.display());
+        let _ = fs::remove_file(&tmp_path);
+    }
+}
+
+/// Marks a file written by `export_settings_bundle`.
+const BUNDLE_FORMAT: &str = "translator-settings-bundle";
+
+/// Settings plus optional user data, exported as a single JSON file.
+#[derive(Serialize, Deserialize)]
+struct SettingsBundle {
+    format: String,
+    /// Kept as raw JSON so imports go through the same migrations as
+    /// `settings.json`.
+    settings: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    glossary: Option<Glossary>,
+}
+
+pub fn export_settings_bundle(
+    path: &Path,
+    settings: &Settings,
+    glossary: Option<&Glossary>,
+) -> Result<(), String> {
+    let bundle = SettingsBundle {
+        format: BUNDLE_FORMAT.to_string(),
+        settings: serde_json::to_value(settings).expect("settings should serialize"),
+        glossary: glossary.cloned(),
+    };
+    let json = serde_json::to_string_pretty(&bundle).expect("bundle should serialize");
+    fs::write(path, json).map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+/// Reads a bundle written by `export_settings_bundle`. A plain `settings.json`
+/// copied from another device is accepted too.
+pub fn import_settings_bundle(path: &Path) -> Result<(Settings, Option<Glossary>), String> {
+    let contents = fs::read_to_string(path)
+        .map_err(|err| format!("Failed to read {}: {err}", path.display()))?;
+    let Ok(Value::Object(mut stored)) = serde_json::from_str::<Value>(&contents) else {
+        return Err(format!("{} is not a settings file", path.display()));
+    };
+    if stored.get("format").and_then(Value::as_str) != Some(BUNDLE_FORMAT) {
+        return Ok((settings_from_object(stored), None));
+    }
+
+    let Some(Value::Object(settings)) = stored.remove("settings") else {
+        return Err(format!("{} contains no settings", path.display()));
+    };
+    let glossary = match stored.remove("glossary") {
+        Some(glossary) => Some(
+            serde_json::from_value::<Glossary>(glossary)
+                .map_err(|err| format!("Invalid glossary in {}: {err}", path.display()))?,
+        ),
+        None => None,
+    };
+    Ok((settings_from_object(settings), glossary))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrates_version_0_files() {
+        let settings = parse_settings(
+            r#"{
+                "default_from_code": "de",
+                "default_to_code": "fr",
+                "ocr_background_mode": "Dark Background",
+                "ocr_min_confidence": 60,
+                "disable_ocr": true
+            }"#,
+        );
+        assert_eq!(settings.schema_version, SETTINGS_SCHEMA_VERSION);
+        assert_eq!(settings.default_from_code, "de");
+        assert_eq!(settings.default_to_code, "fr");
+        assert_eq!(
+            settings.ocr_background_mode,
+            OcrBackgroundMode::DarkBackground
+        );
+        assert_eq!(settings.ocr_min_confidence, 60);
+        assert!(settings.disable_ocr);
+        assert_eq!(settings.api_server_port, 5000);
+    }
+
+    #[test]
+    fn invalid_fields_fall_back_individually() {
+        let settings = parse_settings(
+            r#"{
+                "schema_version": 1,
+                "default_from_code": "es",
+                "ocr_background_mode": "sepia",
+                "ocr_min_confidence": "high",
+                "ocr_max_image_size": 99999,
+                "tts_playback_speed": 1.5,
+                "recent_pairs": [{ "from": "es", "to": "en" }],
+                "unknown_future_field": true
+            }"#,
+        );
+        assert_eq!(settings.default_from_code, "es");
+        assert_eq!(settings.ocr_background_mode, OcrBackgroundMode::AutoDetect);
+        assert_eq!(settings.ocr_min_confidence, 75);
+        assert_eq!(settings.ocr_max_image_size, 4000);
+        assert_eq!(settings.tts_playback_speed, 1.5);
+        assert_eq!(settings.recent_pairs, [LanguagePair::new("es", "en")]);
+    }
+
+    #[test]
+    fn unreadable_files_use_defaults() {
+        assert_eq!(parse_settings("{ not json"), Settings::default());
+        assert_eq!(parse_settings("[1, 2]"), Settings::default());
+    }
+
+    #[test]
+    fn save_then_load_round_trips() {
+        let dir = std::env::temp_dir().join(format!("translator-settings-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let config_dir = dir.to_str().unwrap();
+        let settings = Settings {
+            default_from_code: "ja".to_string(),
+            ocr_background_mode: OcrBackgroundMode::LightBackground,
+            pinned_pairs: vec![LanguagePair::new("ja", "en")],
+            ..Settings::default()
+        };
+
+        save_settings(config_dir, &settings);
+        assert_eq!(load_settings(config_dir), settings);
+        assert!(!dir.join("settings.json.tmp").exists());
+        assert!(load_external_change(config_dir).is_none());
+        fs::write(
+            dir.join(SETTINGS_FILE_NAME),
+            r#"{ "schema_version": 1, "default_from_code": "ko" }"#,
+        )
+        .unwrap();
+        let external = load_external_change(config_dir).unwrap();
+        assert_eq!(external.default_from_code, "ko");
+        save_settings(config_dir, &settings);
+
+        let mut glossary = Glossary::default();
+        glossary.upsert(
+            "ja",
+            "en",
+            vec![crate::glossary::GlossaryEntry {
+                source: "東京".to_string(),
+                target: None,
+            }],
+        );
+        let bundle_path = dir.join("bundle.json");
+        export_settings_bundle(&bundle_path, &settings, Some(&glossary)).unwrap();
+        let (imported, imported_glossary) = import_settings_bundle(&bundle_path).unwrap();
+        assert_eq!(imported, settings);
+        assert_eq!(imported_glossary.unwrap().entries("ja", "en").len(), 1);
+
+        let (plain, no_glossary) = import_settings_bundle(&dir.join(SETTINGS_FILE_NAME)).unwrap();
+        assert_eq!(plain, settings);
+        assert!(no_glossary.is_none());
+        fs::remove_dir_all(&dir).unwrap();
+    }
 }
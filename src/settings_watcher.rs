@@ -0,0 +1,116 @@
+use std::ffi::CString;
+use std::fs::File;
+use std::io::Read;
+use std::os::fd::FromRawFd;
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crate::settings::{SETTINGS_FILE_NAME, Settings, load_external_change};
+
+/// Size of `struct inotify_event` without its trailing name.
+const EVENT_HEADER_LEN: usize = size_of::<libc::inotify_event>();
+/// Gives a writer time to finish a burst of writes before the file is read.
+const SETTLE_DELAY: Duration = Duration::from_millis(200);
+
+/// Watches the config directory and hands externally edited settings to
+/// `on_change`. Our own saves are recognised and skipped.
+pub fn spawn_settings_watcher(config_dir: String, on_change: Arc<dyn Fn(Settings) + Send + Sync>) {
+    let mut events = match watch_directory(&config_dir) {
+        Ok(events) => events,
+        Err(err) => {
+            eprintln!("settings watcher: {err}");
+            return;
+        }
+    };
+
+    thread::spawn(move || {
+        let mut buffer = [0u8; 4096];
+        loop {
+            let len = match events.read(&mut buffer) {
+                Ok(len) => len,
+                Err(err) => {
+                    eprintln!("settings watcher: read failed: {err}");
+                    return;
+                }
+            };
+            if !changed_names(&buffer[..len]).any(|name| name == SETTINGS_FILE_NAME) {
+                continue;
+            }
+            thread::sleep(SETTLE_DELAY);
+            if let Some(settings) = load_external_change(&config_dir) {
+                on_change(settings);
+            }
+        }
+    });
+}
+
+fn watch_directory(config_dir: &str) -> Result<File, String> {
+    std::fs::create_dir_all(config_dir)
+        .map_err(|err| format!("failed to create {config_dir}: {err}"))?;
+    let path = CString::new(config_dir).map_err(|err| err.to_string())?;
+
+    let fd = unsafe { libc::inotify_init1(libc::IN_CLOEXEC) };
+    if fd < 0 {
+        return Err(format!(
+            "inotify_init1 failed: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    // Owned from here on, so the descriptor is closed on every return path.
+    let events = unsafe { File::from_raw_fd(fd) };
+    // Editors and config tools either rewrite in place or rename a temp file over it.
+    let watch = unsafe {
+        libc::inotify_add_watch(fd, path.as_ptr(), libc::IN_CLOSE_WRITE | libc::IN_MOVED_TO)
+    };
+    if watch < 0 {
+        return Err(format!(
+            "failed to watch {config_dir}: {}",
+            std::io::Error::last_os_error()
+        ));
+    }
+    Ok(events)
+}
+
+/// File names from a buffer of `struct inotify_event` records.
+fn changed_names(buffer: &[u8]) -> impl Iterator<Item = &str> {
+    let mut offset = 0;
+    std::iter::from_fn(move || {
+        let header = buffer.get(offset..offset + EVENT_HEADER_LEN)?;
+        // The byte buffer gives no alignment guarantee for the record.
+        let event =
+            unsafe { std::ptr::read_unaligned(header.as_ptr().cast::<libc::inotify_event>()) };
+        let name_len = event.len as usize;
+        let name = buffer.get(offset + EVENT_HEADER_LEN..offset + EVENT_HEADER_LEN + name_len)?;
+        offset += EVENT_HEADER_LEN + name_len;
+        // Names are NUL-padded to the record's alignment.
+        let end = name
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(name.len());
+        Some(std::str::from_utf8(&name[..end]).unwrap_or_default())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_names_from_event_records() {
+        let mut buffer = Vec::new();
+        for (name, padded_len) in [("settings.json.tmp", 32), ("settings.json", 16)] {
+            buffer.extend_from_slice(&1i32.to_ne_bytes());
+            buffer.extend_from_slice(&libc::IN_MOVED_TO.to_ne_bytes());
+            buffer.extend_from_slice(&0u32.to_ne_bytes());
+            buffer.extend_from_slice(&(padded_len as u32).to_ne_bytes());
+            let mut name = name.as_bytes().to_vec();
+            name.resize(padded_len, 0);
+            buffer.extend_from_slice(&name);
+        }
+
+        let names = changed_names(&buffer).collect::<Vec<_>>();
+        assert_eq!(names, ["settings.json.tmp", "settings.json"]);
+        assert_eq!(changed_names(&buffer[..20]).count(), 0);
+    }
+}
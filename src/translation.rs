@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+use translator::TranslatorSession;
+
+use crate::glossary::{Glossary, GlossaryEntry};
+use crate::i18n::tr_args;
+use crate::placeholders::{self, MaskedText, PlaceholderProtection};
+
+const PIVOT_CODE: &str = "en";
+
+#[derive(Clone, Debug, Default)]
+pub struct TranslationOptions {
+    pub protection: PlaceholderProtection,
+    pub glossary: Vec<GlossaryEntry>,
+    /// Hand non-English pairs to the session in one call, which pivots
+    /// through English internally, instead of running both hops here.
+    pub direct: bool,
+}
+
+/// The user's translation preferences for requests that name their own
+/// language pair, such as those from the HTTP API.
+#[derive(Clone, Debug, Default)]
+pub struct TranslationSettings {
+    pub protection: PlaceholderProtection,
+    pub glossary: Glossary,
+    pub direct: bool,
+}
+
+impl TranslationSettings {
+    pub fn options(&self, from: &str, to: &str) -> TranslationOptions {
+        TranslationOptions {
+            protection: self.protection,
+            glossary: self.glossary.entries(from, to).to_vec(),
+            direct: self.direct,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TranslationHop {
+    pub from: String,
+    pub to: String,
+    /// Language the session pivots through inside this single call, since
+    /// installed models only go to or from English.
+    pub via: Option<String>,
+    pub elapsed: Duration,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct TranslationRoute {
+    pub hops: Vec<TranslationHop>,
+    pub intermediate_text: Option<String>,
+}
+
+/// Displays as stable English for the HTTP and D-Bus APIs; the UI shows
+/// `localized()` instead.
+#[derive(Clone, Debug)]
+pub enum TranslationError {
+    MissingPair { from: String, to: String },
+    Engine(String),
+}
+
+impl TranslationError {
+    pub fn localized(&self) -> String {
+        match self {
+            Self::MissingPair { from, to } => tr_args(
+                "Missing installed language pair {from}->{to}",
+                &[("from", from), ("to", to)],
+            ),
+            Self::Engine(message) => message.clone(),
+        }
+    }
+}
+
+impl fmt::Display for TranslationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingPair { from, to } => {
+                write!(f, "Missing installed language pair {from}->{to}")
+            }
+            Self::Engine(message) => f.write_str(message),
+        }
+    }
+}
+
+pub struct TranslationOutput {
+    pub text: String,
+    pub route: TranslationRoute,
+}
+
+/// One of several outputs requested from the same source text.
+#[derive(Clone, Debug, Default)]
+pub struct TranslationTarget {
+    pub to: String,
+    pub options: TranslationOptions,
+}
+
+pub fn translate_text(
+    session: &TranslatorSession,
+    from: &str,
+    to: &str,
+    text: &str,
+    options: &TranslationOptions,
+) -> Result<TranslationOutput, TranslationError> {
+    let masked = placeholders::mask(text, &options.protection, &options.glossary);
+    let pivot = if uses_pivot(from, to, options) {
+        Some(translate_hop(session, from, PIVOT_CODE, &masked.text)?)
+    } else {
+        None
+    };
+    finish_translation(session, from, to, &masked, pivot)
+}
+
+/// Translates `text` into every target. The source->English hop is run once per
+/// distinct masked input and shared by all pivoted targets; the remaining hops
+/// run in parallel on the current rayon pool.
+pub fn translate_text_multi(
+    session: &TranslatorSession,
+    from: &str,
+    text: &str,
+    targets: &[TranslationTarget],
+) -> Vec<Result<TranslationOutput, TranslationError>> {
+    let masked = targets
+        .iter()
+        .map(|target| {
+            placeholders::mask(text, &target.options.protection, &target.options.glossary)
+        })
+        .collect::<Vec<_>>();
+
+    let mut pivots = HashMap::new();
+    for (target, masked) in targets.iter().zip(&masked) {
+        if uses_pivot(from, &target.to, &target.options) {
+            pivots
+                .entry(masked.text.clone())
+                .or_insert_with(|| translate_hop(session, from, PIVOT_CODE, &masked.text));
+        }
+    }
+
+    targets
+        .par_iter()
+        .zip(masked.par_iter())
+        .map(|(target, masked)| {
+            let pivot = if uses_pivot(from, &target.to, &target.options) {
+                Some(pivots[&masked.text].clone()?)
+            } else {
+                None
+            };
+            finish_translation(session, from, &target.to, masked, pivot)
+        })
+        .collect()
+}
+
+fn uses_pivot(from: &str, to: &str, options: &TranslationOptions) -> bool {
+    !options.direct && from != to && from != PIVOT_CODE && to != PIVOT_CODE
+}
+
+fn finish_translation(
+    session: &TranslatorSession,
+    from: &str,
+    to: &str,
+    masked: &MaskedText,
+    pivot: Option<(String, TranslationHop)>,
+) -> Result<TranslationOutput, TranslationError> {
+    let mut route = TranslationRoute::default();
+    let translated = match pivot {
+        Some((intermediate, first_hop)) => {
+            let (translated, second_hop) = translate_hop(session, PIVOT_CODE, to, &intermediate)?;
+            route.hops = vec![first_hop, second_hop];
+            route.intermediate_text = Some(masked.restore(&intermediate));
+            translated
+        }
+        None => {
+            let (translated, hop) = translate_hop(session, from, to, &masked.text)?;
+            route.hops = vec![hop];
+            translated
+        }
+    };
+
+    Ok(TranslationOutput {
+        text: masked.restore(&translated),
+        route,
+    })
+}
+
+fn translate_hop(
+    session: &TranslatorSession,
+    from: &str,
+    to: &str,
+    text: &str,
+) -> Result<(String, TranslationHop), TranslationError> {
+    let start = Instant::now();
+    let translated = session.translate_text(from, to, text).map_err(|error| {
+        if error.is_missing_asset() {
+            TranslationError::MissingPair {
+                from: from.to_string(),
+                to: to.to_string(),
+            }
+        } else {
+            TranslationError::Engine(error.message)
+        }
+    })?;
+    let hop = TranslationHop {
+        from: from.to_string(),
+        to: to.to_string(),
+        via: (from != to && from != PIVOT_CODE && to != PIVOT_CODE).then(|| PIVOT_CODE.to_string()),
+        elapsed: start.elapsed(),
+    };
+    Ok((translated, hop))
+}
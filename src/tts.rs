@@ -7,6 +7,7 @@ use std::time::{Duration, Instant};
 
 use translator::{PcmAudio, SpeechChunk, TranslatorSession, TtsVoiceOption};
 
+use crate::i18n::tr;
 use crate::pulse::PulsePlaybackStream;
 use crate::ui::UiCallbacks;
 
@@ -55,7 +56,7 @@ pub fn load_tts_voices(
                 available: false,
                 voices: Vec::new(),
                 selected_voice_name: String::new(),
-                selected_voice_display_name: "Default".to_string(),
+                selected_voice_display_name: tr("Default"),
             });
         }
     };
@@ -83,13 +84,13 @@ pub fn load_tts_voices(
         .unwrap_or_default();
 
     let selected_voice_display_name = if selected_voice_name.is_empty() || voices.len() <= 1 {
-        "Default".to_string()
+        tr("Default")
     } else {
         voices
             .iter()
             .find(|voice| voice.name == selected_voice_name)
             .map(|voice| voice.display_name.clone())
-            .unwrap_or_else(|| "Default".to_string())
+            .unwrap_or_else(|| tr("Default"))
     };
 
     Ok(TtsVoiceRefresh {
@@ -1,15 +1,21 @@
 mod callbacks;
+mod clipboard;
 mod core;
 mod dictionary;
+mod glossary;
 mod image;
+mod language_pairs;
 mod languages;
+mod multi_target;
+mod settings_transfer;
 mod transliteration;
 mod tts;
 mod types;
 
 pub use callbacks::{UiCallbacks, create_ui_callbacks};
 pub use types::{
-    DictionaryPopupRowItem, ImageOverlayListItem, LanguageListItem, ManageLanguageListItem,
+    DetectionCandidateListItem, DictionaryPopupRowItem, ExtraOutputListItem, GlossaryListItem,
+    ImageOverlayListItem, LanguageListItem, LanguagePairListItem, ManageLanguageListItem,
     ManageTtsVoicePackListItem, TtsVoiceListItem, argb_to_qml_color,
 };
 
@@ -20,7 +26,11 @@ use std::sync::mpsc::Sender;
 use translator::tarkka::WordWithTaggedEntries;
 
 use crate::IoEvent;
+use crate::detection::LanguageDetection;
+use crate::glossary::Glossary;
+use crate::language_pairs::LanguagePair;
 use crate::model::{FeatureKind, Language, Screen};
+use crate::reading_order::Rect;
 
 #[derive(QObject, Default)]
 pub struct AppBridge {
@@ -50,6 +60,23 @@ pub struct AppBridge {
 
     pub image_viewer_open: qt_property!(bool; NOTIFY image_viewer_open_changed),
     pub image_viewer_open_changed: qt_signal!(),
+    /// Image OCR extracts the text without translating it.
+    pub ocr_only: qt_property!(bool; NOTIFY ocr_only_changed),
+    pub ocr_only_changed: qt_signal!(),
+    /// Drawing on the image adds a region to read instead of reading it all.
+    pub select_regions: qt_property!(bool; NOTIFY select_regions_changed),
+    pub select_regions_changed: qt_signal!(),
+    /// JSON array of `{x, y, width, height}` in processed-image pixels.
+    pub image_regions: qt_property!(QString; NOTIFY image_regions_changed),
+    pub image_regions_changed: qt_signal!(),
+    /// Taps on the image mark page corners for perspective correction.
+    pub picking_corners: qt_property!(bool; NOTIFY picking_corners_changed),
+    pub picking_corners_changed: qt_signal!(),
+    /// JSON array of `{x, y}` corners tapped so far.
+    pub corner_points: qt_property!(QString; NOTIFY corner_points_changed),
+    pub corner_points_changed: qt_signal!(),
+    pub perspective_corrected: qt_property!(bool; NOTIFY perspective_corrected_changed),
+    pub perspective_corrected_changed: qt_signal!(),
 
     pub tts_available: qt_property!(bool; NOTIFY tts_available_changed),
     pub tts_available_changed: qt_signal!(),
@@ -67,6 +94,12 @@ pub struct AppBridge {
     pub share_image_url: qt_property!(QString; NOTIFY share_image_url_changed),
     pub share_image_url_changed: qt_signal!(),
 
+    /// Pages of the open PDF or image set; 1 for a single image.
+    pub page_count: qt_property!(i32; NOTIFY page_count_changed),
+    pub page_count_changed: qt_signal!(),
+    pub current_page: qt_property!(i32; NOTIFY current_page_changed),
+    pub current_page_changed: qt_signal!(),
+
     pub processed_image_width: qt_property!(f32; NOTIFY processed_image_width_changed),
     pub processed_image_width_changed: qt_signal!(),
 
@@ -84,6 +117,7 @@ pub struct AppBridge {
 
     pub installed_to_language_names: qt_property!(QStringList; NOTIFY installed_to_language_names_changed),
     pub installed_to_language_names_changed: qt_signal!(),
+    pub installed_to_language_codes: qt_property!(QStringList; NOTIFY installed_to_language_names_changed),
 
     pub swap_enabled: qt_property!(bool; NOTIFY swap_enabled_changed),
     pub swap_enabled_changed: qt_signal!(),
@@ -100,6 +134,12 @@ pub struct AppBridge {
     pub show_missing_card: qt_property!(bool; NOTIFY show_missing_card_changed),
     pub show_missing_card_changed: qt_signal!(),
 
+    pub show_detection_candidates: qt_property!(bool; NOTIFY show_detection_candidates_changed),
+    pub show_detection_candidates_changed: qt_signal!(),
+
+    pub mixed_languages_text: qt_property!(QString; NOTIFY mixed_languages_text_changed),
+    pub mixed_languages_text_changed: qt_signal!(),
+
     pub active_tab: qt_property!(i32; NOTIFY active_tab_changed),
     pub active_tab_changed: qt_signal!(),
 
@@ -112,6 +152,8 @@ pub struct AppBridge {
     pub manage_tts_picker_language_name: qt_property!(QString; NOTIFY manage_tts_picker_language_name_changed),
     pub manage_tts_picker_language_name_changed: qt_signal!(),
 
+    pub raise_requested: qt_signal!(),
+
     pub installed_languages_model: qt_property!(RefCell<SimpleListModel<LanguageListItem>>; CONST),
     pub available_languages_model: qt_property!(RefCell<SimpleListModel<LanguageListItem>>; CONST),
     pub manage_languages_model: qt_property!(RefCell<SimpleListModel<ManageLanguageListItem>>; CONST),
@@ -119,6 +161,23 @@ pub struct AppBridge {
     pub image_overlay_model: qt_property!(RefCell<SimpleListModel<ImageOverlayListItem>>; CONST),
     pub tts_voice_options_model: qt_property!(RefCell<SimpleListModel<TtsVoiceListItem>>; CONST),
     pub dictionary_popup_rows_model: qt_property!(RefCell<SimpleListModel<DictionaryPopupRowItem>>; CONST),
+    pub glossary_model: qt_property!(RefCell<SimpleListModel<GlossaryListItem>>; CONST),
+    pub extra_outputs_model: qt_property!(RefCell<SimpleListModel<ExtraOutputListItem>>; CONST),
+    pub detection_candidates_model: qt_property!(RefCell<SimpleListModel<DetectionCandidateListItem>>; CONST),
+    pub language_pairs_model: qt_property!(RefCell<SimpleListModel<LanguagePairListItem>>; CONST),
+
+    pub extra_target_language_names: qt_property!(QStringList; NOTIFY extra_target_language_names_changed),
+    pub extra_target_language_names_changed: qt_signal!(),
+    pub extra_target_language_codes: qt_property!(QStringList; NOTIFY extra_target_language_names_changed),
+
+    pub glossary_pair_label: qt_property!(QString; NOTIFY glossary_pair_label_changed),
+    pub glossary_pair_label_changed: qt_signal!(),
+
+    pub glossary_status: qt_property!(QString; NOTIFY glossary_status_changed),
+    pub glossary_status_changed: qt_signal!(),
+
+    pub settings_transfer_status: qt_property!(QString; NOTIFY settings_transfer_status_changed),
+    pub settings_transfer_status_changed: qt_signal!(),
 
     pub desktop_mode: qt_property!(bool; CONST),
     pub automation_enabled: qt_property!(bool; CONST),
@@ -130,6 +189,10 @@ pub struct AppBridge {
 
     pub ocr_background_mode: qt_property!(QString; NOTIFY ocr_background_mode_changed),
     pub ocr_background_mode_changed: qt_signal!(),
+    pub ocr_reading_order: qt_property!(QString; NOTIFY ocr_reading_order_changed),
+    pub ocr_reading_order_changed: qt_signal!(),
+    pub ocr_straighten: qt_property!(bool; NOTIFY ocr_straighten_changed),
+    pub ocr_straighten_changed: qt_signal!(),
 
     pub ocr_min_confidence: qt_property!(i32; NOTIFY ocr_min_confidence_changed),
     pub ocr_min_confidence_changed: qt_signal!(),
@@ -149,6 +212,51 @@ pub struct AppBridge {
     pub show_transliteration_input: qt_property!(bool; NOTIFY show_transliteration_input_changed),
     pub show_transliteration_input_changed: qt_signal!(),
 
+    pub protect_braces_placeholders: qt_property!(bool; NOTIFY placeholder_protection_changed),
+    pub protect_printf_placeholders: qt_property!(bool; NOTIFY placeholder_protection_changed),
+    pub protect_urls: qt_property!(bool; NOTIFY placeholder_protection_changed),
+    pub protect_emails: qt_property!(bool; NOTIFY placeholder_protection_changed),
+    pub protect_numbers_with_units: qt_property!(bool; NOTIFY placeholder_protection_changed),
+    pub placeholder_protection_changed: qt_signal!(),
+
+    pub translate_clipboard_on_focus: qt_property!(bool; NOTIFY translate_clipboard_on_focus_changed),
+    pub translate_clipboard_on_focus_changed: qt_signal!(),
+
+    pub clipboard_use_selection: qt_property!(bool; NOTIFY clipboard_use_selection_changed),
+    pub clipboard_use_selection_changed: qt_signal!(),
+
+    pub api_server_enabled: qt_property!(bool; NOTIFY api_server_enabled_changed),
+    pub api_server_enabled_changed: qt_signal!(),
+
+    pub api_server_port: qt_property!(i32; NOTIFY api_server_port_changed),
+    pub api_server_port_changed: qt_signal!(),
+
+    pub api_server_status: qt_property!(QString; NOTIFY api_server_status_changed),
+    pub api_server_status_changed: qt_signal!(),
+
+    pub batch_workers: qt_property!(i32; NOTIFY batch_workers_changed),
+    pub batch_workers_changed: qt_signal!(),
+    pub max_batch_workers: qt_property!(i32; CONST),
+
+    pub direct_translation: qt_property!(bool; NOTIFY direct_translation_changed),
+    pub direct_translation_changed: qt_signal!(),
+
+    pub auto_switch_source: qt_property!(bool; NOTIFY auto_switch_source_changed),
+    pub auto_switch_source_changed: qt_signal!(),
+
+    pub native_language_names: qt_property!(bool; NOTIFY native_language_names_changed),
+    pub native_language_names_changed: qt_signal!(),
+
+    pub assumed_source_language_name: qt_property!(QString; NOTIFY assumed_source_language_name_changed),
+    pub assumed_source_language_name_changed: qt_signal!(),
+
+    pub translation_route: qt_property!(QString; NOTIFY translation_route_changed),
+    pub translation_route_changed: qt_signal!(),
+
+    pub translation_intermediate_text: qt_property!(QString; NOTIFY translation_route_changed),
+    pub translation_timings: qt_property!(QString; NOTIFY translation_route_changed),
+    pub translation_hop_count: qt_property!(i32; NOTIFY translation_route_changed),
+
     pub tts_playback_speed: qt_property!(f32; NOTIFY tts_playback_speed_changed),
     pub tts_playback_speed_changed: qt_signal!(),
 
@@ -297,16 +405,9 @@ pub struct AppBridge {
                 false
             };
 
-            let visible = self.manage_filter.is_empty()
-                || self
-                    .find_language_by_code(&code)
-                    .map(|language| {
-                        language
-                            .name
-                            .to_lowercase()
-                            .contains(self.manage_filter.as_str())
-                    })
-                    .unwrap_or(false);
+            let visible = self
+                .find_language_by_code(&code)
+                .is_some_and(|language| self.matches_manage_filter(language));
 
             if visible && let Some(language) = self.find_language_by_code(&code).cloned() {
                 types::update_manage_progress_item(
@@ -382,6 +483,26 @@ pub struct AppBridge {
             self.missing_language_action_impl();
         }
     ),
+    pub tr: qt_method!(
+        fn tr(&self, text: QString) -> QString {
+            QString::from(crate::i18n::tr(&text.to_string()))
+        }
+    ),
+    pub select_language_pair: qt_method!(
+        fn select_language_pair(&mut self, from: QString, to: QString) {
+            self.select_language_pair_impl(&from.to_string(), &to.to_string());
+        }
+    ),
+    pub toggle_pinned_pair: qt_method!(
+        fn toggle_pinned_pair(&mut self, from: QString, to: QString) {
+            self.toggle_pinned_pair_impl(&from.to_string(), &to.to_string());
+        }
+    ),
+    pub choose_detected_language: qt_method!(
+        fn choose_detected_language(&mut self, code: QString) {
+            self.choose_detected_language_impl(&code.to_string());
+        }
+    ),
     pub camera_clicked: qt_method!(
         fn camera_clicked(&self) {
             println!("Camera clicked");
@@ -392,16 +513,100 @@ pub struct AppBridge {
             self.process_image_selection_impl(url.to_string());
         }
     ),
+    pub process_image_files: qt_method!(
+        fn process_image_files(&mut self, urls: QString) {
+            self.process_image_files_impl(urls.to_string());
+        }
+    ),
+    pub show_image_page: qt_method!(
+        fn show_image_page(&mut self, page: i32) {
+            if page >= 0 {
+                self.show_image_page_impl(page as usize);
+            }
+        }
+    ),
+    pub export_page_texts: qt_method!(
+        fn export_page_texts(&self, url: QString) -> QString {
+            QString::from(self.export_page_texts_impl(url.to_string()))
+        }
+    ),
     pub clear_selected_image: qt_method!(
         fn clear_selected_image(&mut self) {
             self.original_image_path.clear();
+            self.set_image_pages_value(Vec::new());
             self.stop_tts();
             self.set_image_mode_value(false);
+            // Text-only reading lasts for one image.
+            self.set_ocr_only_impl(false);
             self.set_image_viewer_open_value(false);
             self.set_selected_image_url_value(String::new());
             self.set_processed_image_value(QImage::default());
             self.set_share_image_url_value(String::new());
             self.set_image_overlay_value(Vec::new(), 0.0, 0.0);
+            self.set_image_regions_value(Vec::new());
+            self.reset_image_corrections();
+        }
+    ),
+    pub set_ocr_only_value: qt_method!(
+        fn set_ocr_only_value(&mut self, value: bool) {
+            self.set_ocr_only_impl(value);
+        }
+    ),
+    pub translate_extracted_text: qt_method!(
+        fn translate_extracted_text(&mut self) {
+            self.translate_extracted_text_impl();
+        }
+    ),
+    pub export_translated_image: qt_method!(
+        fn export_translated_image(&self) -> QString {
+            QString::from(self.export_translated_image_impl())
+        }
+    ),
+    pub edit_image_block: qt_method!(
+        fn edit_image_block(&mut self, index: i32, text: QString) {
+            if index >= 0 {
+                self.edit_image_block_impl(index as usize, text.to_string());
+            }
+        }
+    ),
+    pub set_select_regions_value: qt_method!(
+        fn set_select_regions_value(&mut self, value: bool) {
+            self.set_select_regions_impl(value);
+        }
+    ),
+    pub add_image_region: qt_method!(
+        fn add_image_region(&mut self, x: f64, y: f64, width: f64, height: f64) {
+            self.add_image_region_impl(Rect {
+                x: x.max(0.0) as u32,
+                y: y.max(0.0) as u32,
+                width: width.max(0.0) as u32,
+                height: height.max(0.0) as u32,
+            });
+        }
+    ),
+    pub clear_image_regions: qt_method!(
+        fn clear_image_regions(&mut self) {
+            self.clear_image_regions_impl();
+        }
+    ),
+    pub rotate_image: qt_method!(
+        fn rotate_image(&mut self) {
+            self.rotate_image_impl();
+        }
+    ),
+    pub start_perspective_correction: qt_method!(
+        fn start_perspective_correction(&mut self) {
+            self.start_perspective_correction_impl();
+        }
+    ),
+    pub add_perspective_corner: qt_method!(
+        fn add_perspective_corner(&mut self, x: f64, y: f64) {
+            self.add_perspective_corner_impl(x as f32, y as f32);
+        }
+    ),
+    pub reset_perspective: qt_method!(
+        fn reset_perspective(&mut self) {
+            self.reset_perspective_impl();
         }
     ),
     pub open_image_viewer: qt_method!(
@@ -469,6 +674,46 @@ pub struct AppBridge {
             self.select_dictionary_popup_entry_impl(index);
         }
     ),
+    pub add_glossary_entry: qt_method!(
+        fn add_glossary_entry(&mut self, source: QString, target: QString) {
+            self.add_glossary_entry_impl(source.to_string(), target.to_string());
+        }
+    ),
+    pub remove_glossary_entry: qt_method!(
+        fn remove_glossary_entry(&mut self, index: i32) {
+            self.remove_glossary_entry_impl(index);
+        }
+    ),
+    pub import_glossary: qt_method!(
+        fn import_glossary(&mut self, url: QString) {
+            self.import_glossary_impl(url.to_string());
+        }
+    ),
+    pub export_settings: qt_method!(
+        fn export_settings(&mut self, url: QString, include_glossary: bool) -> QString {
+            QString::from(self.export_settings_impl(url.to_string(), include_glossary))
+        }
+    ),
+    pub import_settings: qt_method!(
+        fn import_settings(&mut self, url: QString) {
+            self.import_settings_impl(url.to_string());
+        }
+    ),
+    pub reset_settings: qt_method!(
+        fn reset_settings(&mut self) {
+            self.reset_settings_impl();
+        }
+    ),
+    pub set_extra_target_language: qt_method!(
+        fn set_extra_target_language(&mut self, code: QString, enabled: bool) {
+            self.set_extra_target_language_impl(code.to_string(), enabled);
+        }
+    ),
+    pub speak_extra_output: qt_method!(
+        fn speak_extra_output(&mut self, index: i32) {
+            self.speak_extra_output_impl(index);
+        }
+    ),
     pub download_tts_pack: qt_method!(
         fn download_tts_pack(&mut self, pack_id: QString) {
             if self.manage_tts_picker_language_code.is_empty() {
@@ -493,6 +738,24 @@ pub struct AppBridge {
             }
         }
     ),
+    pub set_ocr_reading_order_value: qt_method!(
+        fn set_ocr_reading_order_value(&mut self, value: QString) {
+            if self.ocr_reading_order != value {
+                self.ocr_reading_order = value;
+                self.ocr_reading_order_changed();
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_ocr_straighten_value: qt_method!(
+        fn set_ocr_straighten_value(&mut self, value: bool) {
+            if self.ocr_straighten != value {
+                self.ocr_straighten = value;
+                self.ocr_straighten_changed();
+                self.persist_settings();
+            }
+        }
+    ),
     pub set_ocr_min_confidence_value: qt_method!(
         fn set_ocr_min_confidence_value(&mut self, value: i32) {
             if self.ocr_min_confidence != value {
@@ -535,6 +798,124 @@ pub struct AppBridge {
                 self.show_transliteration_output = value;
                 self.show_transliteration_output_changed();
                 self.refresh_output_transliteration();
+                self.refresh_extra_outputs_model();
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_placeholder_protection_value: qt_method!(
+        fn set_placeholder_protection_value(&mut self, pattern: QString, value: bool) {
+            self.set_placeholder_protection_impl(&pattern.to_string(), value);
+        }
+    ),
+    pub open_launch: qt_method!(
+        fn open_launch(&mut self, text: QString, file: QString) {
+            let given = |value: QString| Some(value.to_string()).filter(|value| !value.is_empty());
+            self.apply_launch_request(crate::cli::LaunchRequest {
+                text: given(text),
+                file: given(file),
+                ..Default::default()
+            });
+        }
+    ),
+    pub translate_clipboard: qt_method!(
+        fn translate_clipboard(&mut self) {
+            self.translate_clipboard_impl(false);
+        }
+    ),
+    pub window_activated: qt_method!(
+        fn window_activated(&mut self) {
+            if self.translate_clipboard_on_focus
+                && self.current_screen == Screen::Translation.as_i32()
+            {
+                self.translate_clipboard_impl(true);
+            }
+        }
+    ),
+    pub set_translate_clipboard_on_focus_value: qt_method!(
+        fn set_translate_clipboard_on_focus_value(&mut self, value: bool) {
+            if self.translate_clipboard_on_focus != value {
+                self.translate_clipboard_on_focus = value;
+                self.translate_clipboard_on_focus_changed();
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_clipboard_use_selection_value: qt_method!(
+        fn set_clipboard_use_selection_value(&mut self, value: bool) {
+            if self.clipboard_use_selection != value {
+                self.clipboard_use_selection = value;
+                self.clipboard_use_selection_changed();
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_api_server_enabled_value: qt_method!(
+        fn set_api_server_enabled_value(&mut self, value: bool) {
+            if self.api_server_enabled != value {
+                self.api_server_enabled = value;
+                self.api_server_enabled_changed();
+                self.send_api_server_config();
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_api_server_port_value: qt_method!(
+        fn set_api_server_port_value(&mut self, value: i32) {
+            if !(1024..=65535).contains(&value) {
+                return;
+            }
+            if self.api_server_port != value {
+                self.api_server_port = value;
+                self.api_server_port_changed();
+                self.send_api_server_config();
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_batch_workers_value: qt_method!(
+        fn set_batch_workers_value(&mut self, value: i32) {
+            let value = value.clamp(0, self.max_batch_workers);
+            if self.batch_workers != value {
+                self.batch_workers = value;
+                self.batch_workers_changed();
+                self.send_io(IoEvent::SetBatchWorkers(value as usize));
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_native_language_names_value: qt_method!(
+        fn set_native_language_names_value(&mut self, value: bool) {
+            if self.native_language_names != value {
+                self.native_language_names = value;
+                self.native_language_names_changed();
+                let languages = std::mem::take(&mut self.all_languages);
+                self.set_languages_value(languages);
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_auto_switch_source_value: qt_method!(
+        fn set_auto_switch_source_value(&mut self, value: bool) {
+            if self.auto_switch_source != value {
+                self.auto_switch_source = value;
+                self.auto_switch_source_changed();
+                self.refresh_detected_language();
+                if !self.image_mode {
+                    self.retranslate();
+                }
+                self.persist_settings();
+            }
+        }
+    ),
+    pub set_direct_translation_value: qt_method!(
+        fn set_direct_translation_value(&mut self, value: bool) {
+            if self.direct_translation != value {
+                self.direct_translation = value;
+                self.direct_translation_changed();
+                if !self.image_mode {
+                    self.retranslate();
+                }
                 self.persist_settings();
             }
         }
@@ -554,6 +935,7 @@ pub struct AppBridge {
     source_language_code: String,
     target_language_code: String,
     detected_language_code: String,
+    language_detection: LanguageDetection,
     previous_screen: Screen,
     bus_tx: Option<Sender<IoEvent>>,
     session: Option<std::sync::Arc<translator::TranslatorSession>>,
@@ -563,9 +945,28 @@ pub struct AppBridge {
     tts_voice_overrides: BTreeMap<String, String>,
     tts_prewarmed_language_code: String,
     original_image_path: String,
+    image_pages: Vec<String>,
+    /// Recognized and translated text of each page, filled in as pages are read.
+    page_texts: Vec<(String, String)>,
+    /// Generation of `page_texts`; see `IoEvent::ImageTranslationRequest`.
+    page_generation: u64,
+    image_overlay_items: Vec<ImageOverlayListItem>,
+    image_region_rects: Vec<Rect>,
+    image_quarter_turns: u8,
+    corner_taps: Vec<(f32, f32)>,
+    perspective_corners: Option<[(f32, f32); 4]>,
     manage_filter: String,
     expanded_languages: HashSet<String>,
     manage_tts_picker_language_code: String,
     dictionary_popup_lookup_language_code: String,
     dictionary_popup_data: Option<WordWithTaggedEntries>,
+    glossary: Glossary,
+    extra_target_codes: Vec<String>,
+    recent_pairs: Vec<LanguagePair>,
+    pinned_pairs: Vec<LanguagePair>,
+    extra_outputs: Vec<(String, String)>,
+    last_clipboard_text: String,
+    /// Set while edits made by other programs are applied, so they aren't
+    /// written back over the file.
+    applying_external_settings: bool,
 }
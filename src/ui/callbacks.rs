@@ -1,7 +1,11 @@
 use qmetaobject::*;
 use std::sync::Arc;
 
+use crate::cli::LaunchRequest;
+use crate::detection::LanguageDetection;
 use crate::model::{FeatureKind, Language};
+use crate::settings::Settings;
+use crate::translation::TranslationRoute;
 
 use super::{AppBridge, ImageOverlayListItem, TtsVoiceListItem};
 
@@ -15,7 +19,20 @@ pub struct UiCallbacks {
     pub set_tts_voices: Arc<dyn Fn(bool, Vec<TtsVoiceListItem>, String, String) + Send + Sync>,
     pub set_processed_image: Arc<dyn Fn(QImage) + Send + Sync>,
     pub set_image_overlay: Arc<dyn Fn(Vec<ImageOverlayListItem>, f32, f32) + Send + Sync>,
-    pub set_detected_language_code: Arc<dyn Fn(String) + Send + Sync>,
+    pub set_image_block_translation:
+        Arc<dyn Fn(usize, String, Result<String, String>) + Send + Sync>,
+    pub set_image_page_text: Arc<dyn Fn(u64, usize, String, String) + Send + Sync>,
+    pub set_image_pages: Arc<dyn Fn(Vec<String>) + Send + Sync>,
+    pub set_language_detection: Arc<dyn Fn(LanguageDetection) + Send + Sync>,
+    pub set_assumed_source_language: Arc<dyn Fn(String) + Send + Sync>,
+    pub set_translation_route: Arc<dyn Fn(TranslationRoute) + Send + Sync>,
+    pub set_extra_outputs: Arc<dyn Fn(Vec<(String, String)>) + Send + Sync>,
+    pub set_api_server_status: Arc<dyn Fn(String) + Send + Sync>,
+    pub present_text: Arc<dyn Fn(String) + Send + Sync>,
+    pub translate_clipboard: Arc<dyn Fn() + Send + Sync>,
+    pub speak_text: Arc<dyn Fn(String, String) + Send + Sync>,
+    pub open_launch_request: Arc<dyn Fn(LaunchRequest) + Send + Sync>,
+    pub apply_external_settings: Arc<dyn Fn(Settings) + Send + Sync>,
 }
 
 pub fn create_ui_callbacks(app: QPointer<AppBridge>) -> UiCallbacks {
@@ -81,10 +98,99 @@ pub fn create_ui_callbacks(app: QPointer<AppBridge>) -> UiCallbacks {
         }
     });
 
+    let image_block_app = app.clone();
+    let set_image_block_translation =
+        queued_callback(move |args: (usize, String, Result<String, String>)| {
+            if let Some(app) = image_block_app.as_pinned() {
+                app.borrow_mut()
+                    .set_image_block_translation_value(args.0, args.1, args.2);
+            }
+        });
+
+    let page_text_app = app.clone();
+    let set_image_page_text = queued_callback(move |args: (u64, usize, String, String)| {
+        if let Some(app) = page_text_app.as_pinned() {
+            app.borrow_mut()
+                .set_image_page_text_value(args.0, args.1, args.2, args.3);
+        }
+    });
+
+    let pages_app = app.clone();
+    let set_image_pages = queued_callback(move |pages: Vec<String>| {
+        if let Some(app) = pages_app.as_pinned() {
+            app.borrow_mut().open_image_pages(pages);
+        }
+    });
+
     let detected_app = app.clone();
-    let set_detected_language_code = queued_callback(move |code: String| {
+    let set_language_detection = queued_callback(move |detection: LanguageDetection| {
         if let Some(app) = detected_app.as_pinned() {
-            app.borrow_mut().set_detected_language_code_value(&code);
+            app.borrow_mut().set_language_detection_value(detection);
+        }
+    });
+
+    let assumed_app = app.clone();
+    let set_assumed_source_language = queued_callback(move |code: String| {
+        if let Some(app) = assumed_app.as_pinned() {
+            app.borrow_mut().set_assumed_source_language_value(code);
+        }
+    });
+
+    let route_app = app.clone();
+    let set_translation_route = queued_callback(move |route: TranslationRoute| {
+        if let Some(app) = route_app.as_pinned() {
+            app.borrow_mut().set_translation_route_value(route);
+        }
+    });
+
+    let extra_outputs_app = app.clone();
+    let set_extra_outputs = queued_callback(move |outputs: Vec<(String, String)>| {
+        if let Some(app) = extra_outputs_app.as_pinned() {
+            app.borrow_mut().set_extra_outputs_value(outputs);
+        }
+    });
+
+    let api_server_app = app.clone();
+    let set_api_server_status = queued_callback(move |status: String| {
+        if let Some(app) = api_server_app.as_pinned() {
+            app.borrow_mut().set_api_server_status_value(status);
+        }
+    });
+
+    let present_app = app.clone();
+    let present_text = queued_callback(move |text: String| {
+        if let Some(app) = present_app.as_pinned() {
+            app.borrow_mut().present_text_value(text);
+        }
+    });
+
+    let clipboard_app = app.clone();
+    let translate_clipboard = queued_callback(move |()| {
+        if let Some(app) = clipboard_app.as_pinned() {
+            app.borrow_mut().translate_clipboard_impl(false);
+        }
+    });
+
+    let speak_app = app.clone();
+    let speak_text = queued_callback(move |args: (String, String)| {
+        if let Some(app) = speak_app.as_pinned() {
+            app.borrow_mut().speak_text_impl(args.0, args.1);
+        }
+    });
+
+    let launch_app = app.clone();
+    let open_launch_request = queued_callback(move |request: LaunchRequest| {
+        if let Some(app) = launch_app.as_pinned() {
+            let mut app = app.borrow_mut();
+            app.apply_launch_request(request);
+            app.raise_requested();
+        }
+    });
+
+    let settings_app = app.clone();
+    let apply_external_settings = queued_callback(move |settings: Settings| {
+        if let Some(app) = settings_app.as_pinned() {
+            app.borrow_mut().apply_external_settings_impl(settings);
         }
     });
 
@@ -105,6 +211,22 @@ pub fn create_ui_callbacks(app: QPointer<AppBridge>) -> UiCallbacks {
         set_image_overlay: Arc::new(move |items, width, height| {
             set_image_overlay((items, width, height))
         }),
-        set_detected_language_code: Arc::new(set_detected_language_code),
+        set_image_block_translation: Arc::new(move |index, source_text, translated| {
+            set_image_block_translation((index, source_text, translated))
+        }),
+        set_image_page_text: Arc::new(move |generation, page, source_text, translated_text| {
+            set_image_page_text((generation, page, source_text, translated_text))
+        }),
+        set_image_pages: Arc::new(set_image_pages),
+        set_language_detection: Arc::new(set_language_detection),
+        set_assumed_source_language: Arc::new(set_assumed_source_language),
+        set_translation_route: Arc::new(set_translation_route),
+        set_extra_outputs: Arc::new(set_extra_outputs),
+        set_api_server_status: Arc::new(set_api_server_status),
+        present_text: Arc::new(present_text),
+        translate_clipboard: Arc::new(move || translate_clipboard(())),
+        speak_text: Arc::new(move |language_code, text| speak_text((language_code, text))),
+        open_launch_request: Arc::new(open_launch_request),
+        apply_external_settings: Arc::new(apply_external_settings),
     }
 }
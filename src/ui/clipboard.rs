@@ -0,0 +1,41 @@
+use cpp::cpp;
+use qmetaobject::QString;
+
+use super::AppBridge;
+
+cpp! {{
+    #include <QtGui/QGuiApplication>
+    #include <QtGui/QClipboard>
+}}
+
+impl AppBridge {
+    /// Translates the clipboard (or primary selection). Focus-triggered reads
+    /// skip text that was already translated.
+    pub(crate) fn translate_clipboard_impl(&mut self, only_if_changed: bool) {
+        let mut text = read_clipboard_text(self.clipboard_use_selection);
+        if text.trim().is_empty() && self.clipboard_use_selection {
+            text = read_clipboard_text(false);
+        }
+        if text.trim().is_empty() || (only_if_changed && text == self.last_clipboard_text) {
+            return;
+        }
+        self.last_clipboard_text = text.clone();
+        self.present_text_value(text);
+    }
+}
+
+/// Reads the clipboard, or the X11/Wayland primary selection when `selection`
+/// is set and the platform supports it.
+fn read_clipboard_text(selection: bool) -> String {
+    let text = cpp!(unsafe [selection as "bool"] -> QString as "QString" {
+        QClipboard* clipboard = QGuiApplication::clipboard();
+        if (!clipboard) {
+            return QString();
+        }
+        if (selection && clipboard->supportsSelection()) {
+            return clipboard->text(QClipboard::Selection);
+        }
+        return clipboard->text(QClipboard::Clipboard);
+    });
+    text.to_string()
+}
@@ -4,8 +4,12 @@ use qmetaobject::QString;
 use translator::TranslatorSession;
 
 use crate::IoEvent;
-use crate::model::{FeatureKind, Language, Screen};
-use crate::settings::{Settings, save_settings};
+use crate::detection::LanguageDetection;
+use crate::glossary::load_glossary;
+use crate::model::{FeatureKind, Language, OcrBackgroundMode, OcrReadingOrder, Screen};
+use crate::placeholders::PlaceholderProtection;
+use crate::settings::{SETTINGS_SCHEMA_VERSION, Settings, save_settings};
+use crate::translation::{TranslationOptions, TranslationRoute, TranslationSettings};
 
 use super::AppBridge;
 
@@ -59,60 +63,248 @@ impl AppBridge {
         app.source_language_name = QString::from("English");
         app.target_language_name = QString::from("English");
 
-        app.disable_auto_detect = settings.disable_auto_detect;
-        app.ocr_background_mode = QString::from(settings.ocr_background_mode);
-        app.ocr_min_confidence = settings.ocr_min_confidence;
-        app.ocr_max_image_size = settings.ocr_max_image_size;
-        app.catalog_index_url = QString::from(settings.catalog_index_url);
-        app.disable_ocr = settings.disable_ocr;
-        app.show_transliteration_output = settings.show_transliteration_output;
-        app.show_transliteration_input = settings.show_transliteration_input;
-        app.tts_playback_speed = settings.tts_playback_speed.clamp(0.5, 2.0);
-        app.tts_voice_overrides = settings.tts_voice_overrides.clone();
+        app.max_batch_workers = std::thread::available_parallelism()
+            .map(|count| count.get() as i32)
+            .unwrap_or(1);
+        app.glossary = load_glossary(&app.config_dir);
+        app.load_settings_fields(&settings);
 
         app.set_languages_value(languages);
+        app.select_settings_languages(&settings);
+        app.send_translation_settings();
 
-        if let Some(lang) = app
+        app
+    }
+
+    /// Copies stored preferences into the bridge without emitting signals.
+    pub(crate) fn load_settings_fields(&mut self, settings: &Settings) {
+        self.disable_auto_detect = settings.disable_auto_detect;
+        self.auto_switch_source = settings.auto_switch_source;
+        self.native_language_names = settings.native_language_names;
+        self.ocr_background_mode = QString::from(settings.ocr_background_mode.label());
+        self.ocr_reading_order = QString::from(settings.ocr_reading_order.label());
+        self.ocr_straighten = settings.ocr_straighten;
+        self.ocr_min_confidence = settings.ocr_min_confidence;
+        self.ocr_max_image_size = settings.ocr_max_image_size;
+        self.catalog_index_url = QString::from(settings.catalog_index_url.clone());
+        self.disable_ocr = settings.disable_ocr;
+        self.show_transliteration_output = settings.show_transliteration_output;
+        self.show_transliteration_input = settings.show_transliteration_input;
+        self.tts_playback_speed = settings.tts_playback_speed.clamp(0.5, 2.0);
+        self.tts_voice_overrides = settings.tts_voice_overrides.clone();
+        self.protect_braces_placeholders = settings.placeholder_protection.braces;
+        self.protect_printf_placeholders = settings.placeholder_protection.printf;
+        self.protect_urls = settings.placeholder_protection.urls;
+        self.protect_emails = settings.placeholder_protection.emails;
+        self.protect_numbers_with_units = settings.placeholder_protection.units;
+        self.batch_workers = (settings.batch_workers as i32).clamp(0, self.max_batch_workers);
+        self.direct_translation = settings.direct_translation;
+        self.api_server_enabled = settings.api_server_enabled;
+        self.api_server_port = settings.api_server_port as i32;
+        self.translate_clipboard_on_focus = settings.translate_clipboard_on_focus;
+        self.clipboard_use_selection = settings.clipboard_use_selection;
+        self.extra_target_codes = settings.extra_target_codes.clone();
+        self.recent_pairs = settings.recent_pairs.clone();
+        self.pinned_pairs = settings.pinned_pairs.clone();
+    }
+
+    pub(crate) fn select_settings_languages(&mut self, settings: &Settings) {
+        if let Some(lang) = self
             .find_language_by_code(&settings.default_from_code)
             .cloned()
         {
-            app.set_source_language_by_name(lang.name);
+            self.set_source_language_by_name(lang.name);
         }
-        if let Some(lang) = app
+        if let Some(lang) = self
             .find_language_by_code(&settings.default_to_code)
             .cloned()
         {
-            app.set_target_language_by_name(lang.name);
+            self.set_target_language_by_name(lang.name);
         }
+    }
 
-        app
+    /// Unknown labels (e.g. from an outdated QML model) fall back to auto-detect.
+    pub(crate) fn ocr_background_mode_value(&self) -> OcrBackgroundMode {
+        OcrBackgroundMode::from_label(&self.ocr_background_mode.to_string()).unwrap_or_default()
+    }
+
+    pub(crate) fn ocr_reading_order_value(&self) -> OcrReadingOrder {
+        OcrReadingOrder::from_label(&self.ocr_reading_order.to_string()).unwrap_or_default()
+    }
+
+    pub(crate) fn ocr_reading_order_for_source(&self) -> OcrReadingOrder {
+        let script = self
+            .find_language_by_code(&self.source_language_code)
+            .map(|language| language.script.as_str())
+            .unwrap_or_default();
+        self.ocr_reading_order_value().for_script(script)
     }
 
     pub(crate) fn persist_settings(&self) {
-        let settings = Settings {
+        if !self.applying_external_settings {
+            save_settings(&self.config_dir, &self.current_settings());
+        }
+        self.send_translation_settings();
+    }
+
+    /// Keeps requests from outside the UI translating with the same options.
+    pub(crate) fn send_translation_settings(&self) {
+        self.send_io(IoEvent::SetTranslationSettings(TranslationSettings {
+            protection: self.placeholder_protection(),
+            glossary: self.glossary.clone(),
+            direct: self.direct_translation,
+        }));
+    }
+
+    pub(crate) fn current_settings(&self) -> Settings {
+        Settings {
+            schema_version: SETTINGS_SCHEMA_VERSION,
             default_from_code: self.source_language_code.clone(),
             default_to_code: self.target_language_code.clone(),
-            ocr_background_mode: self.ocr_background_mode.to_string(),
+            ocr_background_mode: self.ocr_background_mode_value(),
+            ocr_reading_order: self.ocr_reading_order_value(),
+            ocr_straighten: self.ocr_straighten,
             ocr_min_confidence: self.ocr_min_confidence,
             ocr_max_image_size: self.ocr_max_image_size,
             catalog_index_url: self.catalog_index_url.to_string(),
             disable_ocr: self.disable_ocr,
             disable_auto_detect: self.disable_auto_detect,
+            auto_switch_source: self.auto_switch_source,
+            native_language_names: self.native_language_names,
             show_transliteration_output: self.show_transliteration_output,
             show_transliteration_input: self.show_transliteration_input,
             tts_playback_speed: self.tts_playback_speed,
             tts_voice_overrides: self.tts_voice_overrides.clone(),
+            placeholder_protection: self.placeholder_protection(),
+            direct_translation: self.direct_translation,
+            extra_target_codes: self.extra_target_codes.clone(),
+            recent_pairs: self.recent_pairs.clone(),
+            pinned_pairs: self.pinned_pairs.clone(),
+            batch_workers: self.batch_workers.max(0) as u32,
+            api_server_enabled: self.api_server_enabled,
+            api_server_port: self.api_server_port as u16,
+            translate_clipboard_on_focus: self.translate_clipboard_on_focus,
+            clipboard_use_selection: self.clipboard_use_selection,
+        }
+    }
+
+    pub(crate) fn translation_options(&self) -> TranslationOptions {
+        self.translation_options_for(&self.target_language_code)
+    }
+
+    pub(crate) fn translation_options_for(&self, to: &str) -> TranslationOptions {
+        TranslationOptions {
+            protection: self.placeholder_protection(),
+            glossary: self
+                .glossary
+                .entries(&self.source_language_code, to)
+                .to_vec(),
+            direct: self.direct_translation,
+        }
+    }
+
+    pub(crate) fn placeholder_protection(&self) -> PlaceholderProtection {
+        PlaceholderProtection {
+            braces: self.protect_braces_placeholders,
+            printf: self.protect_printf_placeholders,
+            urls: self.protect_urls,
+            emails: self.protect_emails,
+            units: self.protect_numbers_with_units,
+        }
+    }
+
+    pub(crate) fn set_placeholder_protection_impl(&mut self, pattern: &str, value: bool) {
+        let field = match pattern {
+            "braces" => &mut self.protect_braces_placeholders,
+            "printf" => &mut self.protect_printf_placeholders,
+            "urls" => &mut self.protect_urls,
+            "emails" => &mut self.protect_emails,
+            "units" => &mut self.protect_numbers_with_units,
+            _ => {
+                eprintln!("unknown placeholder pattern '{pattern}'");
+                return;
+            }
         };
-        save_settings(&self.config_dir, &settings);
+        if *field != value {
+            *field = value;
+            self.placeholder_protection_changed();
+            if !self.image_mode {
+                self.retranslate();
+            }
+            self.persist_settings();
+        }
     }
 
-    pub(crate) fn set_detected_language_code_value(&mut self, code: &str) {
-        if self.detected_language_code != code {
-            self.detected_language_code = code.to_string();
+    pub(crate) fn set_translation_route_value(&mut self, route: TranslationRoute) {
+        let language_name = |code: &str| {
+            self.find_language_by_code(code)
+                .map(|language| language.name.clone())
+                .unwrap_or_else(|| code.to_string())
+        };
+        let mut names = Vec::new();
+        let mut timings = Vec::new();
+        for hop in &route.hops {
+            if names.is_empty() {
+                names.push(language_name(&hop.from));
+            }
+            let codes = match &hop.via {
+                Some(via) => {
+                    names.push(language_name(via));
+                    format!("{} → {} → {}", hop.from, via, hop.to)
+                }
+                None => format!("{} → {}", hop.from, hop.to),
+            };
+            names.push(language_name(&hop.to));
+            timings.push(format!("{codes} {} ms", hop.elapsed.as_millis()));
+        }
+
+        self.translation_route = QString::from(names.join(" → "));
+        self.translation_intermediate_text =
+            QString::from(route.intermediate_text.unwrap_or_default());
+        self.translation_timings = QString::from(timings.join(" · "));
+        self.translation_hop_count = names.len().saturating_sub(1) as i32;
+        self.translation_route_changed();
+    }
+
+    pub(crate) fn send_api_server_config(&self) {
+        self.send_io(IoEvent::SetApiServer {
+            enabled: self.api_server_enabled,
+            port: self.api_server_port as u16,
+        });
+    }
+
+    pub(crate) fn set_api_server_status_value(&mut self, status: String) {
+        let status = QString::from(status);
+        if self.api_server_status != status {
+            self.api_server_status = status;
+            self.api_server_status_changed();
+        }
+    }
+
+    pub(crate) fn set_language_detection_value(&mut self, detection: LanguageDetection) {
+        if self.language_detection != detection {
+            self.detected_language_code = detection.best_code().unwrap_or_default().to_string();
+            self.language_detection = detection;
             self.refresh_detected_language();
         }
     }
 
+    pub(crate) fn set_assumed_source_language_value(&mut self, code: String) {
+        let name = self
+            .find_language_by_code(&code)
+            .map(|language| QString::from(language.name.clone()))
+            .unwrap_or_default();
+        if self.assumed_source_language_name != name {
+            self.assumed_source_language_name = name;
+            self.assumed_source_language_name_changed();
+        }
+    }
+
+    /// Whether requests may translate from the detected language instead of the selected one.
+    pub(crate) fn auto_source_enabled(&self) -> bool {
+        self.auto_switch_source && !self.disable_auto_detect
+    }
+
     pub(crate) fn set_current_screen(&mut self, screen: Screen) {
         let screen = screen.as_i32();
         if self.current_screen != screen {
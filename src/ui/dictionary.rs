@@ -1,6 +1,8 @@
 use qmetaobject::QString;
 use translator::tarkka::WordWithTaggedEntries;
 
+use crate::i18n::{tr, tr_args};
+
 use super::{AppBridge, types::DictionaryPopupRowItem};
 
 impl AppBridge {
@@ -18,7 +20,10 @@ impl AppBridge {
             return;
         };
         if !language.dictionary_installed || language.dictionary_code.is_empty() {
-            self.show_toast_impl(format!("No {} dictionary installed", language.name));
+            self.show_toast_impl(tr_args(
+                "No {language} dictionary installed",
+                &[("language", &language.name)],
+            ));
             return;
         }
 
@@ -41,20 +46,23 @@ impl AppBridge {
                     "dictionary lookup: '{}' not found for {}",
                     trimmed, language_code
                 );
-                self.show_toast_impl(format!(
-                    "‘{}’ not found in {} dictionary",
-                    trimmed, language.name
+                self.show_toast_impl(tr_args(
+                    "‘{word}’ not found in {language} dictionary",
+                    &[("word", trimmed), ("language", &language.name)],
                 ));
             }
             Err(error) if error.is_missing_asset() => {
-                self.show_toast_impl(format!("No {} dictionary installed", language.name));
+                self.show_toast_impl(tr_args(
+                    "No {language} dictionary installed",
+                    &[("language", &language.name)],
+                ));
             }
             Err(error) => {
                 eprintln!(
                     "dictionary lookup failed for '{}' ({}): {}",
                     trimmed, language_code, error.message
                 );
-                self.show_toast_impl("Dictionary lookup failed".to_string());
+                self.show_toast_impl(tr("Dictionary lookup failed"));
             }
         }
     }
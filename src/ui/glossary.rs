@@ -0,0 +1,106 @@
+use qmetaobject::QString;
+
+use crate::glossary::{GlossaryEntry, import_glossary_file, save_glossary};
+use crate::i18n::tr_args;
+
+use super::{AppBridge, GlossaryListItem};
+
+impl AppBridge {
+    pub(crate) fn refresh_glossary_model(&mut self) {
+        let items = self
+            .glossary
+            .entries(&self.source_language_code, &self.target_language_code)
+            .iter()
+            .map(|entry| GlossaryListItem {
+                source: QString::from(entry.source.clone()),
+                target: QString::from(entry.target.clone().unwrap_or_default()),
+                keep_original: entry.target.is_none(),
+            })
+            .collect::<Vec<_>>();
+        self.glossary_model.borrow_mut().reset_data(items);
+
+        let label = QString::from(format!(
+            "{} → {}",
+            self.source_language_name, self.target_language_name
+        ));
+        if self.glossary_pair_label != label {
+            self.glossary_pair_label = label;
+            self.glossary_pair_label_changed();
+        }
+    }
+
+    pub(crate) fn add_glossary_entry_impl(&mut self, source: String, target: String) {
+        let added = self.glossary.upsert(
+            &self.source_language_code,
+            &self.target_language_code,
+            vec![GlossaryEntry {
+                source,
+                target: Some(target),
+            }],
+        );
+        if added > 0 {
+            self.glossary_changed();
+        }
+    }
+
+    pub(crate) fn remove_glossary_entry_impl(&mut self, index: i32) {
+        if index >= 0
+            && self.glossary.remove(
+                &self.source_language_code,
+                &self.target_language_code,
+                index as usize,
+            )
+        {
+            self.glossary_changed();
+        }
+    }
+
+    pub(crate) fn import_glossary_impl(&mut self, url: String) {
+        let Some(path) = crate::image_ocr::resolve_local_path(&url) else {
+            return;
+        };
+
+        match import_glossary_file(
+            &path,
+            &self.source_language_code,
+            &self.target_language_code,
+        ) {
+            Ok(entries) => {
+                let count = self.glossary.upsert(
+                    &self.source_language_code,
+                    &self.target_language_code,
+                    entries,
+                );
+                self.set_glossary_status_value(tr_args(
+                    "Imported {count} term(s)",
+                    &[("count", &count.to_string())],
+                ));
+                self.glossary_changed();
+            }
+            Err(message) => {
+                eprintln!("glossary import failed: {message}");
+                self.set_glossary_status_value(message);
+            }
+        }
+    }
+
+    fn glossary_changed(&mut self) {
+        if let Err(message) = save_glossary(&self.config_dir, &self.glossary) {
+            eprintln!("{message}");
+            self.show_toast_impl(message);
+        }
+        self.send_translation_settings();
+        self.refresh_glossary_model();
+        if !self.image_mode {
+            self.retranslate();
+        }
+    }
+
+    fn set_glossary_status_value(&mut self, value: String) {
+        let value = QString::from(value);
+        if self.glossary_status != value {
+            self.glossary_status = value;
+            self.glossary_status_changed();
+        }
+    }
+}
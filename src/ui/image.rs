@@ -1,10 +1,25 @@
+use std::path::{Path, PathBuf};
+
 use qmetaobject::{QImage, QString};
 
 use crate::IoEvent;
-use crate::rendered_image_item::qimage_from_rgba_bytes;
+use crate::cli::LaunchRequest;
+use crate::detection::LanguageDetection;
+use crate::i18n::{tr, tr_args};
+use crate::image_export::burn_in_overlay;
+use crate::image_preprocess::{Preprocess, order_corners};
+use crate::model::Screen;
+use crate::reading_order::Rect;
+use crate::rendered_image_item::{qimage_from_rgba_bytes, save_image};
 
 use super::{AppBridge, ImageOverlayListItem};
 
+/// Shared or saved in place of the original photo once it's translated; JPEG
+/// photos stay JPEG.
+const TRANSLATED_IMAGE_PNG: &str = "translated-image.png";
+const TRANSLATED_IMAGE_JPEG: &str = "translated-image.jpg";
+const PAGE_TEXTS_FILE_NAME: &str = "translated-pages.txt";
+
 impl AppBridge {
     pub(crate) fn set_image_mode_value(&mut self, value: bool) {
         if self.image_mode != value {
@@ -49,6 +64,7 @@ impl AppBridge {
         width: f32,
         height: f32,
     ) {
+        self.image_overlay_items = items.clone();
         self.image_overlay_model.borrow_mut().reset_data(items);
 
         if (self.processed_image_width - width).abs() > f32::EPSILON {
@@ -68,19 +84,94 @@ impl AppBridge {
             self.input_text_changed();
         }
         self.refresh_input_transliteration();
+        if self.image_mode && self.ocr_only {
+            // Extracted text is edited in place until it's sent to translation.
+            return;
+        }
 
         self.stop_tts();
+        if !text.trim().is_empty() {
+            self.remember_current_pair();
+        }
+        self.send_translation_request(text);
+    }
 
-        self.send_io(IoEvent::TranslationRequest {
-            text,
-            from: self.source_language_code.clone(),
-            to: self.target_language_code.clone(),
-        });
+    /// Brings the window forward for an external request, optionally with text
+    /// to translate.
+    pub(crate) fn present_text_value(&mut self, text: String) {
+        if self.has_languages {
+            self.set_current_screen(Screen::Translation);
+        }
+        if !text.is_empty() {
+            if self.image_mode {
+                self.clear_selected_image();
+            }
+            self.process_text_impl(text);
+        }
+        self.raise_requested();
+    }
+
+    /// Applies `--from`/`--to` (codes or names), then opens the file or text.
+    pub(crate) fn apply_launch_request(&mut self, request: LaunchRequest) {
+        if let Some(name) = request
+            .from
+            .as_deref()
+            .and_then(|value| self.find_language_name(value))
+        {
+            self.set_source_language_by_name(name);
+        }
+        if let Some(name) = request
+            .to
+            .as_deref()
+            .and_then(|value| self.find_language_name(value))
+        {
+            self.set_target_language_by_name(name);
+        }
+
+        if let Some(file) = request.file {
+            // Matches the desktop entries: images and PDFs are OCRed, any
+            // other file is opened as plain text.
+            let text_path = crate::image_ocr::resolve_local_path(&file)
+                .filter(|path| path.is_file() && !crate::image_ocr::is_image_or_document(path));
+            match text_path {
+                Some(path) => match std::fs::read_to_string(&path) {
+                    Ok(text) => self.present_text_value(text),
+                    Err(err) => self.set_output_text_value(tr_args(
+                        "Couldn't open {path}: {error}",
+                        &[
+                            ("path", &path.display().to_string()),
+                            ("error", &err.to_string()),
+                        ],
+                    )),
+                },
+                None => {
+                    if self.has_languages {
+                        self.set_current_screen(Screen::Translation);
+                    }
+                    self.process_image_selection_impl(file);
+                }
+            }
+            return;
+        }
+
+        if let Some(text) = request.text {
+            self.present_text_value(text);
+        }
+    }
+
+    fn find_language_name(&self, code_or_name: &str) -> Option<String> {
+        self.all_languages
+            .iter()
+            .find(|language| {
+                language.code.eq_ignore_ascii_case(code_or_name)
+                    || language.name.eq_ignore_ascii_case(code_or_name)
+            })
+            .map(|language| language.name.clone())
     }
 
     pub(crate) fn process_image_selection_impl(&mut self, url: String) {
         if self.disable_ocr {
-            self.set_output_text_value("OCR is disabled in settings".to_string());
+            self.set_output_text_value(tr("OCR is disabled in settings"));
             return;
         }
 
@@ -89,19 +180,199 @@ impl AppBridge {
         }
 
         let Some(path) = crate::image_ocr::resolve_local_path(&url) else {
-            self.set_output_text_value("Couldn't open the selected image".to_string());
+            self.set_output_text_value(tr("Couldn't open the selected image"));
             return;
         };
 
-        let preview =
-            crate::image_ocr::load_preview_rgba(&path, self.ocr_max_image_size.max(0) as u32).ok();
+        let is_pdf = crate::image_ocr::is_pdf(&path);
+        if is_pdf || crate::image_ocr::is_tiff(&path) {
+            // TIFFs may hold several pages; a single page comes back as is.
+            self.stop_tts();
+            if is_pdf {
+                self.set_output_text_value(tr("Rendering PDF pages..."));
+            }
+            self.send_io(IoEvent::DocumentPagesRequest {
+                document_path: path.display().to_string(),
+            });
+            return;
+        }
+        self.open_image_pages(vec![url]);
+    }
+
+    /// Opens several images, one per line of `urls`, as the pages of one
+    /// document.
+    pub(crate) fn process_image_files_impl(&mut self, urls: String) {
+        let urls = urls
+            .lines()
+            .map(str::trim)
+            .filter(|url| !url.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>();
+        if urls.len() == 1 {
+            self.process_image_selection_impl(urls[0].clone());
+            return;
+        }
+        if self.disable_ocr {
+            self.set_output_text_value(tr("OCR is disabled in settings"));
+            return;
+        }
+        self.open_image_pages(urls);
+    }
 
+    /// Shows the first page and reads the others in the background, so their
+    /// text can be exported.
+    pub(crate) fn open_image_pages(&mut self, urls: Vec<String>) {
+        if urls.is_empty() {
+            return;
+        }
+        self.remember_current_pair();
+        self.set_image_pages_value(urls);
+        self.show_image_page_impl(0);
+        self.translate_other_pages();
+    }
+
+    /// Drops what was read of the pages so far; results still on their way
+    /// are ignored.
+    fn reset_page_texts(&mut self) {
+        self.page_generation += 1;
+        self.page_texts = vec![Default::default(); self.image_pages.len()];
+    }
+
+    pub(crate) fn set_image_pages_value(&mut self, urls: Vec<String>) {
+        self.image_pages = urls;
+        self.reset_page_texts();
+        let count = self.image_pages.len() as i32;
+        if self.page_count != count {
+            self.page_count = count;
+            self.page_count_changed();
+        }
+        self.set_current_page_value(0);
+    }
+
+    fn set_current_page_value(&mut self, page: usize) {
+        let page = page as i32;
+        if self.current_page != page {
+            self.current_page = page;
+            self.current_page_changed();
+        }
+    }
+
+    pub(crate) fn show_image_page_impl(&mut self, page: usize) {
+        let Some(url) = self.image_pages.get(page).cloned() else {
+            return;
+        };
+        let Some(path) = crate::image_ocr::resolve_local_path(&url) else {
+            self.set_output_text_value(tr("Couldn't open the selected image"));
+            return;
+        };
+
+        self.set_current_page_value(page);
         self.original_image_path = path.display().to_string();
         self.stop_tts();
         self.set_image_mode_value(true);
         self.set_image_viewer_open_value(false);
         self.set_selected_image_url_value(url.clone());
         self.set_share_image_url_value(url);
+        self.reset_image_corrections();
+        self.set_image_regions_value(Vec::new());
+        self.reload_image_preview();
+        self.set_input_text_value(String::new());
+        self.set_extra_outputs_value(Vec::new());
+        self.read_current_image(true);
+    }
+
+    /// Re-reads every page, the shown one first, after a change that affects
+    /// all of them.
+    fn reread_image_pages(&mut self) {
+        self.reset_page_texts();
+        self.rerun_current_image();
+        self.translate_other_pages();
+    }
+
+    /// Reads every page but the shown one in the background without showing
+    /// the results; only their text is kept.
+    fn translate_other_pages(&mut self) {
+        let corrections = Preprocess {
+            detect_sideways: self.ocr_straighten,
+            deskew: self.ocr_straighten,
+            ..Preprocess::default()
+        };
+        for (page, url) in self.image_pages.iter().enumerate() {
+            if page as i32 == self.current_page {
+                continue;
+            }
+            if let Some(path) = crate::image_ocr::resolve_local_path(url) {
+                self.send_io(self.image_translation_request(
+                    path.display().to_string(),
+                    page,
+                    false,
+                    false,
+                    Vec::new(),
+                    corrections.clone(),
+                ));
+            }
+        }
+    }
+
+    pub(crate) fn set_image_page_text_value(
+        &mut self,
+        generation: u64,
+        page: usize,
+        source_text: String,
+        translated_text: String,
+    ) {
+        if generation != self.page_generation {
+            return;
+        }
+        if let Some(texts) = self.page_texts.get_mut(page) {
+            *texts = (source_text, translated_text);
+        }
+    }
+
+    /// Writes each page's translation, or its recognized text in text-only
+    /// mode, under a page heading to `url` (or the data directory when empty)
+    /// and returns the written file's URL, or an empty string on failure.
+    pub(crate) fn export_page_texts_impl(&self, url: String) -> String {
+        let path = if url.is_empty() {
+            Path::new(&self.data_dir).join(PAGE_TEXTS_FILE_NAME)
+        } else {
+            match crate::image_ocr::resolve_local_path(&url) {
+                Some(path) => path,
+                None => PathBuf::from(url),
+            }
+        };
+        let text = self
+            .page_texts
+            .iter()
+            .enumerate()
+            .map(|(page, (source_text, translated_text))| {
+                let body = if self.ocr_only {
+                    source_text
+                } else {
+                    translated_text
+                };
+                let heading = tr_args("Page {page}", &[("page", &(page + 1).to_string())]);
+                format!("{heading}\n\n{}\n", body.trim())
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+        match std::fs::write(&path, text) {
+            Ok(()) => format!("file://{}", path.display()),
+            Err(err) => {
+                eprintln!("page text export to {} failed: {err}", path.display());
+                String::new()
+            }
+        }
+    }
+
+    /// Shows the current image with its corrections applied, before OCR runs.
+    fn reload_image_preview(&mut self) {
+        let preview = crate::image_ocr::load_preview_rgba(
+            Path::new(&self.original_image_path),
+            self.ocr_max_image_size.max(0) as u32,
+            &self.image_corrections(),
+        )
+        .ok();
         if let Some((rgba_bytes, width, height)) = preview {
             self.set_processed_image_value(qimage_from_rgba_bytes(width, height, &rgba_bytes));
             self.set_image_overlay_value(Vec::new(), width as f32, height as f32);
@@ -109,29 +380,250 @@ impl AppBridge {
             self.set_processed_image_value(QImage::default());
             self.set_image_overlay_value(Vec::new(), 0.0, 0.0);
         }
-        self.set_input_text_value(String::new());
-        self.set_output_text_value("Running OCR...".to_string());
-        self.set_detected_language_code_value("");
+    }
+
+    /// While corners are being picked the image is only turned, since the
+    /// corners refer to that image.
+    fn image_corrections(&self) -> Preprocess {
+        Preprocess {
+            quarter_turns: self.image_quarter_turns,
+            detect_sideways: self.ocr_straighten,
+            deskew: self.ocr_straighten && !self.picking_corners,
+            perspective: self.perspective_corners,
+        }
+    }
+
+    pub(crate) fn reset_image_corrections(&mut self) {
+        self.image_quarter_turns = 0;
+        self.perspective_corners = None;
+        self.corner_taps.clear();
+        self.set_corner_points_value();
+        self.set_picking_corners_value(false);
+        self.set_perspective_corrected_value(false);
+    }
+
+    /// Turns the image another quarter clockwise; earlier corners and regions
+    /// no longer match it.
+    pub(crate) fn rotate_image_impl(&mut self) {
+        if !self.image_mode {
+            return;
+        }
+        let quarter_turns = (self.image_quarter_turns + 1) % 4;
+        self.reset_image_corrections();
+        self.image_quarter_turns = quarter_turns;
+        self.apply_image_corrections();
+    }
+
+    pub(crate) fn start_perspective_correction_impl(&mut self) {
+        if !self.image_mode {
+            return;
+        }
+        self.perspective_corners = None;
+        self.corner_taps.clear();
+        self.set_corner_points_value();
+        self.set_perspective_corrected_value(false);
+        self.set_picking_corners_value(true);
+        self.apply_image_corrections();
+    }
+
+    /// Records one tapped corner; the fourth one applies the correction.
+    pub(crate) fn add_perspective_corner_impl(&mut self, x: f32, y: f32) {
+        if !self.picking_corners {
+            return;
+        }
+        self.corner_taps.push((x, y));
+        if let Ok(taps) = <[(f32, f32); 4]>::try_from(self.corner_taps.as_slice()) {
+            self.perspective_corners = Some(order_corners(taps));
+            self.corner_taps.clear();
+            self.set_picking_corners_value(false);
+            self.set_perspective_corrected_value(true);
+            self.apply_image_corrections();
+        }
+        self.set_corner_points_value();
+    }
+
+    pub(crate) fn reset_perspective_impl(&mut self) {
+        let quarter_turns = self.image_quarter_turns;
+        self.reset_image_corrections();
+        self.image_quarter_turns = quarter_turns;
+        self.apply_image_corrections();
+    }
+
+    fn apply_image_corrections(&mut self) {
+        self.set_image_regions_value(Vec::new());
+        self.reload_image_preview();
+        self.rerun_current_image();
+    }
 
-        self.send_io(IoEvent::ImageTranslationRequest {
-            image_path: self.original_image_path.clone(),
+    fn set_picking_corners_value(&mut self, value: bool) {
+        if self.picking_corners != value {
+            self.picking_corners = value;
+            self.picking_corners_changed();
+        }
+    }
+
+    fn set_perspective_corrected_value(&mut self, value: bool) {
+        if self.perspective_corrected != value {
+            self.perspective_corrected = value;
+            self.perspective_corrected_changed();
+        }
+    }
+
+    fn set_corner_points_value(&mut self) {
+        let json = self
+            .corner_taps
+            .iter()
+            .map(|(x, y)| serde_json::json!({ "x": x, "y": y }))
+            .collect::<Vec<_>>();
+        let json = QString::from(serde_json::Value::Array(json).to_string());
+        if self.corner_points != json {
+            self.corner_points = json;
+            self.corner_points_changed();
+        }
+    }
+
+    pub(crate) fn set_ocr_only_impl(&mut self, value: bool) {
+        if self.ocr_only != value {
+            self.ocr_only = value;
+            self.ocr_only_changed();
+            if self.image_mode {
+                self.reread_image_pages();
+            }
+        }
+    }
+
+    /// Leaves image mode and translates the extracted text as edited.
+    pub(crate) fn translate_extracted_text_impl(&mut self) {
+        let text = self.input_text.to_string();
+        self.clear_selected_image();
+        self.process_text_impl(text);
+    }
+
+    /// Writes the cleaned image with the translations drawn in to the data
+    /// directory and returns its URL; empty when nothing is translated yet.
+    pub(crate) fn export_translated_image_impl(&self) -> String {
+        let translated = self
+            .image_overlay_items
+            .iter()
+            .any(|item| !item.translated_text.to_string().trim().is_empty());
+        if !translated || self.ocr_only {
+            return String::new();
+        }
+
+        let jpeg = Path::new(&self.original_image_path)
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .is_some_and(|extension| matches!(extension.to_lowercase().as_str(), "jpg" | "jpeg"));
+        let (file_name, quality) = if jpeg {
+            (TRANSLATED_IMAGE_JPEG, 92)
+        } else {
+            (TRANSLATED_IMAGE_PNG, -1)
+        };
+        let path = Path::new(&self.data_dir).join(file_name);
+        let image = burn_in_overlay(&self.processed_image, &self.image_overlay_items);
+        if !save_image(&image, &path.display().to_string(), quality) {
+            eprintln!("failed to write translated image to {}", path.display());
+            return String::new();
+        }
+        format!("file://{}", path.display())
+    }
+
+    /// Replaces the recognized text of one overlay block and re-translates just
+    /// that block.
+    pub(crate) fn edit_image_block_impl(&mut self, index: usize, text: String) {
+        let text = text.trim().to_string();
+        let Some(item) = self.image_overlay_items.get_mut(index) else {
+            return;
+        };
+        if item.source_text.to_string() == text {
+            return;
+        }
+        item.source_text = text.clone().into();
+        if self.ocr_only {
+            let item = item.clone();
+            self.image_overlay_model
+                .borrow_mut()
+                .change_line(index, item);
+            self.refresh_image_block_text();
+            return;
+        }
+
+        self.stop_tts();
+        self.send_io(IoEvent::ImageBlockTranslationRequest {
+            index,
+            text,
             from: self.source_language_code.clone(),
             to: self.target_language_code.clone(),
-            min_confidence: self.ocr_min_confidence.max(0) as u32,
-            max_image_size: self.ocr_max_image_size.max(0) as u32,
-            background_mode: self.ocr_background_mode.to_string(),
+            options: self.translation_options(),
         });
     }
 
+    /// Applies a block translation unless the block was edited again or the
+    /// image replaced since it was requested. A failed translation keeps the
+    /// block's previous text and is reported in a toast.
+    pub(crate) fn set_image_block_translation_value(
+        &mut self,
+        index: usize,
+        source_text: String,
+        translated: Result<String, String>,
+    ) {
+        let Some(item) = self.image_overlay_items.get_mut(index) else {
+            return;
+        };
+        if item.source_text.to_string() != source_text {
+            return;
+        }
+        let translated_text = match translated {
+            Ok(text) => text,
+            Err(err) => {
+                self.show_toast_impl(err);
+                return;
+            }
+        };
+        item.translated_text = translated_text.into();
+        let item = item.clone();
+        self.image_overlay_model
+            .borrow_mut()
+            .change_line(index, item);
+        self.refresh_image_block_text();
+    }
+
+    /// Rebuilds the input and output text from the overlay blocks.
+    fn refresh_image_block_text(&mut self) {
+        let joined = |text: fn(&ImageOverlayListItem) -> &QString| {
+            self.image_overlay_items
+                .iter()
+                .map(|item| text(item).to_string())
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+        let input = joined(|item| &item.source_text);
+        let output = joined(|item| &item.translated_text);
+        if let Some(texts) = self.page_texts.get_mut(self.current_page.max(0) as usize) {
+            *texts = (input.clone(), output.clone());
+        }
+        self.set_input_text_value(input);
+        if !self.ocr_only {
+            self.set_output_text_value(output);
+        }
+    }
+
     pub(crate) fn refresh_translation_content(&mut self) {
         if self.image_mode {
-            self.rerun_current_image();
+            self.reread_image_pages();
         } else {
             self.retranslate();
         }
     }
 
     pub(crate) fn rerun_current_image(&mut self) {
+        self.read_current_image(false);
+    }
+
+    /// With `reuse_page`, a page already read in this generation is shown
+    /// without running OCR again.
+    fn read_current_image(&mut self, reuse_page: bool) {
         if self.original_image_path.is_empty() {
             return;
         }
@@ -143,25 +635,109 @@ impl AppBridge {
             self.processed_image_height,
         );
         self.set_image_viewer_open_value(false);
-        self.set_output_text_value("Running OCR...".to_string());
-        self.set_detected_language_code_value("");
+        self.set_language_detection_value(LanguageDetection::default());
+        self.set_assumed_source_language_value(String::new());
+        if self.picking_corners {
+            self.set_output_text_value(tr("Tap the four corners of the page"));
+            return;
+        }
+        if self.select_regions && self.image_region_rects.is_empty() {
+            // Nothing is read until a region is drawn.
+            self.set_output_text_value(tr("Draw a box around the text to read"));
+            return;
+        }
+
+        self.set_output_text_value(tr("Running OCR..."));
+        self.send_io(self.image_translation_request(
+            self.original_image_path.clone(),
+            self.current_page.max(0) as usize,
+            true,
+            reuse_page,
+            self.image_region_rects.clone(),
+            self.image_corrections(),
+        ));
+    }
 
-        self.send_io(IoEvent::ImageTranslationRequest {
-            image_path: self.original_image_path.clone(),
+    fn image_translation_request(
+        &self,
+        image_path: String,
+        page: usize,
+        show: bool,
+        reuse_page: bool,
+        regions: Vec<Rect>,
+        corrections: Preprocess,
+    ) -> IoEvent {
+        IoEvent::ImageTranslationRequest {
+            image_path,
+            generation: self.page_generation,
+            page,
+            show,
+            reuse_page,
             from: self.source_language_code.clone(),
             to: self.target_language_code.clone(),
             min_confidence: self.ocr_min_confidence.max(0) as u32,
             max_image_size: self.ocr_max_image_size.max(0) as u32,
-            background_mode: self.ocr_background_mode.to_string(),
-        });
+            background_mode: self.ocr_background_mode_value(),
+            reading_order: self.ocr_reading_order_for_source(),
+            ocr_only: self.ocr_only,
+            regions,
+            corrections,
+            options: self.translation_options(),
+        }
+    }
+
+    pub(crate) fn set_select_regions_impl(&mut self, value: bool) {
+        if self.select_regions != value {
+            self.select_regions = value;
+            self.select_regions_changed();
+        }
+    }
+
+    /// Adds a region, in processed-image pixels, and reads only the drawn
+    /// regions from now on.
+    pub(crate) fn add_image_region_impl(&mut self, region: Rect) {
+        if !self.image_mode || region.width == 0 || region.height == 0 {
+            return;
+        }
+        let mut regions = self.image_region_rects.clone();
+        regions.push(region);
+        self.set_image_regions_value(regions);
+        self.rerun_current_image();
+    }
+
+    /// Leaves region selection and reads the whole image, unless it already
+    /// was.
+    pub(crate) fn clear_image_regions_impl(&mut self) {
+        self.set_select_regions_impl(false);
+        if self.image_region_rects.is_empty() && !self.image_overlay_items.is_empty() {
+            return;
+        }
+        self.set_image_regions_value(Vec::new());
+        self.rerun_current_image();
+    }
+
+    pub(crate) fn set_image_regions_value(&mut self, regions: Vec<Rect>) {
+        if self.image_region_rects == regions {
+            return;
+        }
+        let json = regions
+            .iter()
+            .map(|region| {
+                serde_json::json!({
+                    "x": region.x,
+                    "y": region.y,
+                    "width": region.width,
+                    "height": region.height,
+                })
+            })
+            .collect::<Vec<_>>();
+        self.image_region_rects = regions;
+        self.image_regions = serde_json::Value::Array(json).to_string().into();
+        self.image_regions_changed();
     }
 
     pub(crate) fn retranslate(&mut self) {
         self.stop_tts();
-        self.send_io(IoEvent::TranslationRequest {
-            text: self.input_text.to_string(),
-            from: self.source_language_code.clone(),
-            to: self.target_language_code.clone(),
-        });
+        self.send_translation_request(self.input_text.to_string());
     }
 }
@@ -0,0 +1,78 @@
+use qmetaobject::QString;
+
+use crate::language_pairs::{LanguagePair, remember_pair};
+
+use super::{AppBridge, LanguagePairListItem};
+
+impl AppBridge {
+    /// Records the current pair as recently used; called when the user submits
+    /// new input rather than on every language change, so half-finished
+    /// selections don't end up in the list.
+    pub(crate) fn remember_current_pair(&mut self) {
+        let pair = LanguagePair::new(&self.source_language_code, &self.target_language_code);
+        if remember_pair(&mut self.recent_pairs, pair) {
+            self.refresh_language_pairs_model();
+            self.persist_settings();
+        }
+    }
+
+    pub(crate) fn select_language_pair_impl(&mut self, from: &str, to: &str) {
+        if !self.is_language_selectable(from, true) || !self.is_language_selectable(to, false) {
+            return;
+        }
+        let name = |code: &str| {
+            self.find_language_by_code(code)
+                .map(|language| language.name.clone())
+                .unwrap_or_default()
+        };
+        let (from_name, to_name) = (name(from), name(to));
+        self.set_source_language_by_name(from_name);
+        self.set_target_language_by_name(to_name);
+    }
+
+    pub(crate) fn toggle_pinned_pair_impl(&mut self, from: &str, to: &str) {
+        let pair = LanguagePair::new(from, to);
+        if self.pinned_pairs.contains(&pair) {
+            self.pinned_pairs.retain(|pinned| *pinned != pair);
+        } else {
+            self.pinned_pairs.push(pair);
+        }
+        self.refresh_language_pairs_model();
+        self.persist_settings();
+    }
+
+    /// Pinned pairs first, then recent ones; pairs whose languages are no
+    /// longer installed are hidden but kept.
+    pub(crate) fn refresh_language_pairs_model(&mut self) {
+        let recent = self
+            .recent_pairs
+            .iter()
+            .filter(|pair| !self.pinned_pairs.contains(pair));
+        let items = self
+            .pinned_pairs
+            .iter()
+            .chain(recent)
+            .filter(|pair| {
+                self.is_language_selectable(&pair.from, true)
+                    && self.is_language_selectable(&pair.to, false)
+            })
+            .map(|pair| self.language_pair_to_list_item(pair))
+            .collect::<Vec<_>>();
+        self.language_pairs_model.borrow_mut().reset_data(items);
+    }
+
+    fn language_pair_to_list_item(&self, pair: &LanguagePair) -> LanguagePairListItem {
+        let name = |code: &str| {
+            self.find_language_by_code(code)
+                .map(|language| language.name.clone())
+                .unwrap_or_else(|| code.to_string())
+        };
+        LanguagePairListItem {
+            from_code: QString::from(pair.from.clone()),
+            to_code: QString::from(pair.to.clone()),
+            label: QString::from(format!("{} → {}", name(&pair.from), name(&pair.to))),
+            pinned: self.pinned_pairs.contains(pair),
+            current: pair.from == self.source_language_code && pair.to == self.target_language_code,
+        }
+    }
+}
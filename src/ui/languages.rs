@@ -1,18 +1,27 @@
 use qmetaobject::{QString, QStringList};
 
+use crate::i18n::{tr, tr_args};
+use crate::language_names::{endonym, matches_query};
 use crate::model::{Direction, FeatureKind, Language, Screen};
 
 use super::AppBridge;
 use super::types::{
-    language_to_list_item, manage_language_to_list_item, update_manage_progress_item,
-    update_progress_list_item,
+    DetectionCandidateListItem, language_to_list_item, manage_language_to_list_item,
+    update_manage_progress_item, update_progress_list_item,
 };
 
 impl AppBridge {
     pub(crate) fn set_languages_value(&mut self, mut languages: Vec<Language>) {
+        for language in &mut languages {
+            language.name = match endonym(&language.code) {
+                Some(endonym) if self.native_language_names => endonym.to_string(),
+                _ => tr(&language.english_name),
+            };
+        }
         languages.sort_by(|left, right| left.name.cmp(&right.name));
         eprintln!("ui.set_languages_value: {} languages", languages.len());
         self.all_languages = languages;
+        self.refresh_selected_language_names();
         self.refresh_language_views();
 
         if self.current_screen == Screen::NoLanguages.as_i32() && self.has_languages {
@@ -51,12 +60,7 @@ impl AppBridge {
             &language,
             true,
         );
-        if self.manage_filter.is_empty()
-            || language
-                .name
-                .to_lowercase()
-                .contains(self.manage_filter.as_str())
-        {
+        if self.matches_manage_filter(&language) {
             update_manage_progress_item(
                 &mut self.manage_languages_model.borrow_mut(),
                 &language,
@@ -82,6 +86,8 @@ impl AppBridge {
             self.stop_tts();
             self.refresh_swap_enabled();
             self.refresh_detected_language();
+            self.refresh_glossary_model();
+            self.refresh_language_pairs_model();
             self.refresh_translation_content();
             self.refresh_input_transliteration();
             self.persist_settings();
@@ -103,6 +109,8 @@ impl AppBridge {
             }
             self.stop_tts();
             self.refresh_swap_enabled();
+            self.refresh_glossary_model();
+            self.refresh_language_pairs_model();
             self.refresh_translation_content();
             self.tts_prewarmed_language_code.clear();
             self.refresh_tts_availability();
@@ -112,6 +120,38 @@ impl AppBridge {
         }
     }
 
+    /// Keeps the selected names in step with `all_languages` after a rename.
+    fn refresh_selected_language_names(&mut self) {
+        let name = |code: &str| {
+            self.find_language_by_code(code)
+                .map(|language| QString::from(language.name.clone()))
+        };
+        let source = name(&self.source_language_code);
+        let target = name(&self.target_language_code);
+        if let Some(qname) = source
+            && self.source_language_name != qname
+        {
+            self.source_language_name = qname;
+            self.source_language_name_changed();
+        }
+        if let Some(qname) = target
+            && self.target_language_name != qname
+        {
+            self.target_language_name = qname;
+            self.target_language_name_changed();
+        }
+    }
+
+    pub(crate) fn matches_manage_filter(&self, language: &Language) -> bool {
+        // The displayed name may be the endonym, so the UI-language name is
+        // checked separately.
+        matches_query(&language.code, &language.english_name, &self.manage_filter)
+            || language.name.to_lowercase().contains(&self.manage_filter)
+            || tr(&language.english_name)
+                .to_lowercase()
+                .contains(&self.manage_filter)
+    }
+
     pub(crate) fn swap_languages_impl(&mut self) {
         let source = self.source_language_name.to_string();
         let target = self.target_language_name.to_string();
@@ -133,13 +173,11 @@ impl AppBridge {
         if detected_code.is_empty() {
             return;
         }
+        self.choose_detected_language_impl(&detected_code);
+    }
 
-        if let Some(language) = self
-            .all_languages
-            .iter()
-            .find(|language| language.code == detected_code)
-            .cloned()
-        {
+    pub(crate) fn choose_detected_language_impl(&mut self, code: &str) {
+        if let Some(language) = self.find_language_by_code(code).cloned() {
             if language.core_installed || language.built_in {
                 self.set_source_language_by_name(language.name);
             } else {
@@ -152,13 +190,7 @@ impl AppBridge {
         let manage_items = self
             .all_languages
             .iter()
-            .filter(|language| {
-                self.manage_filter.is_empty()
-                    || language
-                        .name
-                        .to_lowercase()
-                        .contains(self.manage_filter.as_str())
-            })
+            .filter(|language| self.matches_manage_filter(language))
             .cloned()
             .map(|language| {
                 manage_language_to_list_item(
@@ -192,13 +224,7 @@ impl AppBridge {
         let manage_items = self
             .all_languages
             .iter()
-            .filter(|language| {
-                self.manage_filter.is_empty()
-                    || language
-                        .name
-                        .to_lowercase()
-                        .contains(self.manage_filter.as_str())
-            })
+            .filter(|language| self.matches_manage_filter(language))
             .cloned()
             .map(|language| {
                 manage_language_to_list_item(
@@ -238,14 +264,24 @@ impl AppBridge {
             self.installed_from_language_names_changed();
         }
 
-        let to_names = self
+        let to_languages = self
             .all_languages
             .iter()
             .filter(|language| self.is_language_available(language, false))
+            .collect::<Vec<_>>();
+        let to_names = to_languages
+            .iter()
             .map(|language| QString::from(language.name.clone()))
             .collect::<QStringList>();
-        if self.installed_to_language_names != to_names {
+        let to_codes = to_languages
+            .iter()
+            .map(|language| QString::from(language.code.clone()))
+            .collect::<QStringList>();
+        if self.installed_to_language_names != to_names
+            || self.installed_to_language_codes != to_codes
+        {
             self.installed_to_language_names = to_names;
+            self.installed_to_language_codes = to_codes;
             self.installed_to_language_names_changed();
         }
 
@@ -260,6 +296,9 @@ impl AppBridge {
 
         self.ensure_selected_languages_are_valid();
         self.refresh_swap_enabled();
+        self.refresh_glossary_model();
+        self.refresh_language_pairs_model();
+        self.refresh_extra_target_names();
         self.refresh_detected_language();
         self.tts_prewarmed_language_code.clear();
         self.refresh_tts_availability();
@@ -313,9 +352,11 @@ impl AppBridge {
         let detected = self.find_language_by_code(&self.detected_language_code);
         let (name, installed, progress, show_card) = match detected {
             Some(language) => {
+                let installed = language.core_installed || language.built_in;
                 let show = visible
                     && !matches!(language.direction, Direction::ToOnly)
-                    && language.code != self.source_language_code;
+                    && language.code != self.source_language_code
+                    && !(installed && self.auto_source_enabled());
                 (
                     QString::from(language.name.clone()),
                     language.core_installed || language.built_in,
@@ -342,6 +383,61 @@ impl AppBridge {
             self.show_missing_card = show_card;
             self.show_missing_card_changed();
         }
+        self.refresh_detection_candidates();
+    }
+
+    fn refresh_detection_candidates(&mut self) {
+        let items = self
+            .language_detection
+            .candidates
+            .iter()
+            .filter_map(|candidate| {
+                let language = self.find_language_by_code(&candidate.code)?;
+                (!matches!(language.direction, Direction::ToOnly)
+                    && language.code != self.source_language_code)
+                    .then(|| DetectionCandidateListItem {
+                        code: QString::from(language.code.clone()),
+                        name: QString::from(language.name.clone()),
+                        percent: candidate.percent as i32,
+                        installed: language.core_installed || language.built_in,
+                    })
+            })
+            .collect::<Vec<_>>();
+        let detection = &self.language_detection;
+        let show = !self.disable_auto_detect
+            && !items.is_empty()
+            && (!detection.reliable || detection.is_mixed());
+
+        let mixed_text = if !self.disable_auto_detect && detection.is_mixed() {
+            let names = detection
+                .paragraph_codes
+                .iter()
+                .map(|code| {
+                    self.find_language_by_code(code)
+                        .map(|language| language.name.clone())
+                        .unwrap_or_else(|| code.clone())
+                })
+                .collect::<Vec<_>>();
+            tr_args(
+                "Text mixes {languages}",
+                &[("languages", &names.join(", "))],
+            )
+        } else {
+            String::new()
+        };
+
+        self.detection_candidates_model
+            .borrow_mut()
+            .reset_data(items);
+        if self.show_detection_candidates != show {
+            self.show_detection_candidates = show;
+            self.show_detection_candidates_changed();
+        }
+        let mixed_text = QString::from(mixed_text);
+        if self.mixed_languages_text != mixed_text {
+            self.mixed_languages_text = mixed_text;
+            self.mixed_languages_text_changed();
+        }
     }
 
     pub(crate) fn is_language_available(&self, language: &Language, source: bool) -> bool {
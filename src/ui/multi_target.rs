@@ -0,0 +1,139 @@
+use qmetaobject::{QString, QStringList};
+
+use crate::IoEvent;
+use crate::translation::TranslationTarget;
+
+use super::{AppBridge, ExtraOutputListItem};
+
+impl AppBridge {
+    /// Sends a plain request, or a multi-target one when extra targets are selected.
+    pub(crate) fn send_translation_request(&mut self, text: String) {
+        let extra_codes = self.active_extra_target_codes();
+        if extra_codes.is_empty() {
+            self.set_extra_outputs_value(Vec::new());
+            self.send_io(IoEvent::TranslationRequest {
+                text,
+                from: self.source_language_code.clone(),
+                to: self.target_language_code.clone(),
+                auto_source: self.auto_source_enabled(),
+                options: self.translation_options(),
+            });
+            return;
+        }
+
+        let targets = std::iter::once(self.target_language_code.clone())
+            .chain(extra_codes)
+            .map(|to| TranslationTarget {
+                options: self.translation_options_for(&to),
+                to,
+            })
+            .collect();
+        self.send_io(IoEvent::MultiTranslationRequest {
+            text,
+            from: self.source_language_code.clone(),
+            auto_source: self.auto_source_enabled(),
+            targets,
+        });
+    }
+
+    fn active_extra_target_codes(&self) -> Vec<String> {
+        self.extra_target_codes
+            .iter()
+            .filter(|code| {
+                **code != self.target_language_code
+                    && **code != self.source_language_code
+                    && self.is_language_selectable(code, false)
+            })
+            .cloned()
+            .collect()
+    }
+
+    pub(crate) fn set_extra_target_language_impl(&mut self, code: String, enabled: bool) {
+        if self.find_language_by_code(&code).is_none() {
+            return;
+        }
+
+        let present = self.extra_target_codes.contains(&code);
+        if enabled == present {
+            return;
+        }
+        if enabled {
+            self.extra_target_codes.push(code);
+        } else {
+            self.extra_target_codes.retain(|existing| *existing != code);
+        }
+        self.refresh_extra_target_names();
+        if !self.image_mode {
+            self.retranslate();
+        }
+        self.persist_settings();
+    }
+
+    pub(crate) fn refresh_extra_target_names(&mut self) {
+        let languages = self
+            .extra_target_codes
+            .iter()
+            .filter_map(|code| self.find_language_by_code(code))
+            .collect::<Vec<_>>();
+        let names = languages
+            .iter()
+            .map(|language| QString::from(language.name.clone()))
+            .collect::<QStringList>();
+        let codes = languages
+            .iter()
+            .map(|language| QString::from(language.code.clone()))
+            .collect::<QStringList>();
+        if self.extra_target_language_names != names || self.extra_target_language_codes != codes {
+            self.extra_target_language_names = names;
+            self.extra_target_language_codes = codes;
+            self.extra_target_language_names_changed();
+        }
+    }
+
+    pub(crate) fn set_extra_outputs_value(&mut self, outputs: Vec<(String, String)>) {
+        if outputs.is_empty() && self.extra_outputs.is_empty() {
+            return;
+        }
+        self.extra_outputs = outputs;
+        self.refresh_extra_outputs_model();
+    }
+
+    pub(crate) fn refresh_extra_outputs_model(&mut self) {
+        let items = self
+            .extra_outputs
+            .iter()
+            .map(|(code, text)| {
+                let language = self.find_language_by_code(code);
+                ExtraOutputListItem {
+                    code: QString::from(code.clone()),
+                    name: QString::from(
+                        language
+                            .map(|language| language.name.clone())
+                            .unwrap_or_else(|| code.clone()),
+                    ),
+                    text: QString::from(text.clone()),
+                    transliteration: QString::from(if self.show_transliteration_output {
+                        self.compute_transliteration(text, code)
+                    } else {
+                        String::new()
+                    }),
+                    tts_available: language
+                        .map(|language| language.tts_installed)
+                        .unwrap_or(false),
+                }
+            })
+            .collect::<Vec<_>>();
+        self.extra_outputs_model.borrow_mut().reset_data(items);
+    }
+
+    pub(crate) fn speak_extra_output_impl(&mut self, index: i32) {
+        let Some((code, text)) = usize::try_from(index)
+            .ok()
+            .and_then(|index| self.extra_outputs.get(index))
+            .cloned()
+        else {
+            return;
+        };
+        self.speak_text_impl(code, text);
+    }
+}
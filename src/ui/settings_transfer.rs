@@ -0,0 +1,135 @@
+use std::path::{Path, PathBuf};
+
+use qmetaobject::QString;
+
+use crate::IoEvent;
+use crate::glossary::save_glossary;
+use crate::i18n::{tr, tr_args};
+use crate::settings::{Settings, export_settings_bundle, import_settings_bundle};
+
+use super::AppBridge;
+
+/// Written to the data directory when no destination was picked, e.g. before
+/// handing the file to the content hub.
+const DEFAULT_EXPORT_FILE_NAME: &str = "translator-settings.json";
+
+impl AppBridge {
+    /// Writes the current settings to `url` (or the data directory when empty)
+    /// and returns the written file's URL, or an empty string on failure.
+    pub(crate) fn export_settings_impl(&mut self, url: String, include_glossary: bool) -> String {
+        let path = if url.is_empty() {
+            Path::new(&self.data_dir).join(DEFAULT_EXPORT_FILE_NAME)
+        } else {
+            match crate::image_ocr::resolve_local_path(&url) {
+                Some(path) => path,
+                None => PathBuf::from(url),
+            }
+        };
+        let glossary = include_glossary.then_some(&self.glossary);
+        match export_settings_bundle(&path, &self.current_settings(), glossary) {
+            Ok(()) => {
+                self.set_settings_transfer_status_value(tr_args(
+                    "Settings exported to {path}",
+                    &[("path", &path.display().to_string())],
+                ));
+                format!("file://{}", path.display())
+            }
+            Err(message) => {
+                eprintln!("settings export failed: {message}");
+                self.set_settings_transfer_status_value(message);
+                String::new()
+            }
+        }
+    }
+
+    pub(crate) fn import_settings_impl(&mut self, url: String) {
+        let Some(path) = crate::image_ocr::resolve_local_path(&url) else {
+            return;
+        };
+        match import_settings_bundle(&path) {
+            Ok((settings, glossary)) => {
+                if let Some(glossary) = glossary {
+                    self.glossary = glossary;
+                    if let Err(message) = save_glossary(&self.config_dir, &self.glossary) {
+                        eprintln!("{message}");
+                        self.show_toast_impl(message);
+                    }
+                    self.refresh_glossary_model();
+                }
+                self.apply_settings(settings);
+                self.set_settings_transfer_status_value(tr("Settings imported"));
+            }
+            Err(message) => {
+                eprintln!("settings import failed: {message}");
+                self.set_settings_transfer_status_value(message);
+            }
+        }
+    }
+
+    /// Restores every preference to its default; the glossary is left alone.
+    pub(crate) fn reset_settings_impl(&mut self) {
+        self.apply_settings(Settings::default());
+        self.set_settings_transfer_status_value(tr("Settings reset to defaults"));
+    }
+
+    /// Picks up edits made to `settings.json` by other programs. The file is
+    /// left as written; the next change made in the app saves over it.
+    pub(crate) fn apply_external_settings_impl(&mut self, settings: Settings) {
+        if settings == self.current_settings() {
+            return;
+        }
+        eprintln!("settings: applying external change");
+        self.applying_external_settings = true;
+        self.apply_settings(settings);
+        self.applying_external_settings = false;
+    }
+
+    /// Replaces all preferences at runtime, refreshing whatever depends on them.
+    fn apply_settings(&mut self, settings: Settings) {
+        self.load_settings_fields(&settings);
+        self.disable_auto_detect_changed();
+        self.auto_switch_source_changed();
+        self.native_language_names_changed();
+        self.ocr_background_mode_changed();
+        self.ocr_reading_order_changed();
+        self.ocr_straighten_changed();
+        self.ocr_min_confidence_changed();
+        self.ocr_max_image_size_changed();
+        self.catalog_index_url_changed();
+        self.disable_ocr_changed();
+        self.show_transliteration_output_changed();
+        self.show_transliteration_input_changed();
+        self.tts_playback_speed_changed();
+        self.placeholder_protection_changed();
+        self.batch_workers_changed();
+        self.direct_translation_changed();
+        self.api_server_enabled_changed();
+        self.api_server_port_changed();
+        self.translate_clipboard_on_focus_changed();
+        self.clipboard_use_selection_changed();
+
+        let languages = std::mem::take(&mut self.all_languages);
+        self.set_languages_value(languages);
+        self.select_settings_languages(&settings);
+        self.refresh_extra_target_names();
+        self.refresh_language_pairs_model();
+        self.refresh_detected_language();
+        self.refresh_input_transliteration();
+        self.refresh_output_transliteration();
+        self.reset_tts_voice_selection_state();
+        self.send_api_server_config();
+        self.send_io(IoEvent::SetBatchWorkers(self.batch_workers as usize));
+        if !self.image_mode {
+            self.retranslate();
+        }
+        self.persist_settings();
+    }
+
+    fn set_settings_transfer_status_value(&mut self, value: String) {
+        let value = QString::from(value);
+        if self.settings_transfer_status != value {
+            self.settings_transfer_status = value;
+            self.settings_transfer_status_changed();
+        }
+    }
+}
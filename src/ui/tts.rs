@@ -2,6 +2,7 @@ use qmetaobject::QString;
 
 use crate::IoEvent;
 use crate::catalog_state::format_size;
+use crate::i18n::tr;
 use crate::model::FeatureKind;
 
 use super::{AppBridge, ManageTtsVoicePackListItem, TtsVoiceListItem};
@@ -107,6 +108,26 @@ impl AppBridge {
         });
     }
 
+    /// Speaks `text` with the user's playback speed and voice for the language.
+    pub(crate) fn speak_text_impl(&mut self, language_code: String, text: String) {
+        if text.trim().is_empty() {
+            return;
+        }
+
+        self.stop_tts();
+        let voice_name = self
+            .tts_voice_overrides
+            .get(&language_code)
+            .cloned()
+            .unwrap_or_default();
+        self.send_io(IoEvent::SpeakRequest {
+            language_code,
+            text,
+            speech_speed: self.tts_playback_speed.clamp(0.5, 2.0),
+            voice_name,
+        });
+    }
+
     pub(crate) fn prepare_tts_options_impl(&mut self) {
         if self.tts_available {
             eprintln!(
@@ -156,7 +177,7 @@ impl AppBridge {
                     let quality_text = voice
                         .quality
                         .clone()
-                        .unwrap_or_else(|| "Default quality".to_string());
+                        .unwrap_or_else(|| tr("Default quality"));
                     ManageTtsVoicePackListItem {
                         pack_id: voice.pack_id.into(),
                         region_display_name: region_display_name.clone().into(),
@@ -257,7 +278,7 @@ impl AppBridge {
             self.tts_selected_voice_name_changed();
         }
 
-        let selected_display_name = QString::from("Default");
+        let selected_display_name = QString::from(tr("Default"));
         if self.tts_selected_voice_display_name != selected_display_name {
             self.tts_selected_voice_display_name = selected_display_name;
             self.tts_selected_voice_display_name_changed();
@@ -266,14 +287,14 @@ impl AppBridge {
 
     pub(crate) fn apply_tts_voice_selection_preview(&mut self, selected_voice_name: &str) {
         let display_name = if selected_voice_name.is_empty() {
-            "Default".to_string()
+            tr("Default")
         } else {
             self.tts_voice_options_model
                 .borrow()
                 .iter()
                 .find(|item| item.name.to_string() == selected_voice_name)
                 .map(|item| item.display_name.to_string())
-                .unwrap_or_else(|| "Default".to_string())
+                .unwrap_or_else(|| tr("Default"))
         };
 
         let selected_name = QString::from(selected_voice_name);
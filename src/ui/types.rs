@@ -42,9 +42,37 @@ pub struct ImageOverlayListItem {
     pub block_height: f32,
     pub suggested_font_size_px: f32,
     pub line_rects: QString,
+    pub source_text: QString,
     pub translated_text: QString,
     pub background_color: QString,
     pub foreground_color: QString,
+    pub right_to_left: bool,
+}
+
+#[derive(Clone, Default, SimpleListItem)]
+pub struct ExtraOutputListItem {
+    pub code: QString,
+    pub name: QString,
+    pub text: QString,
+    pub transliteration: QString,
+    pub tts_available: bool,
+}
+
+#[derive(Clone, Default, SimpleListItem)]
+pub struct LanguagePairListItem {
+    pub from_code: QString,
+    pub to_code: QString,
+    pub label: QString,
+    pub pinned: bool,
+    pub current: bool,
+}
+
+#[derive(Clone, Default, SimpleListItem)]
+pub struct DetectionCandidateListItem {
+    pub code: QString,
+    pub name: QString,
+    pub percent: i32,
+    pub installed: bool,
 }
 
 #[derive(Clone, Default, SimpleListItem)]
@@ -59,6 +87,13 @@ pub struct DictionaryPopupRowItem {
     pub text: QString,
 }
 
+#[derive(Clone, Default, SimpleListItem)]
+pub struct GlossaryListItem {
+    pub source: QString,
+    pub target: QString,
+    pub keep_original: bool,
+}
+
 #[derive(Clone, Default, SimpleListItem)]
 pub struct ManageTtsVoicePackListItem {
     pub pack_id: QString,